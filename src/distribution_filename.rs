@@ -0,0 +1,272 @@
+//! Parsing of wheel and sdist filenames, as published in `PyPIData::releases`
+//! and `urls` (e.g. `numpy-1.26.4-cp312-cp312-manylinux_2_17_x86_64.whl`);
+//! other artifacts found there, such as the installer
+//! `numpy-1.0.1.dev3460.win32-py2.4.exe`, are rejected. Wheel names follow
+//! the binary distribution spec:
+//! <https://packaging.python.org/en/latest/specifications/binary-distribution-format/#file-name-convention>
+
+/// A wheel tag triple, e.g. `(cp312, cp312, manylinux_2_17_x86_64)`.
+pub type WheelTag = (String, String, String);
+
+/// A parsed wheel filename:
+/// `{distribution}-{version}(-{build tag})?-{python tag}-{abi tag}-{platform tag}.whl`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WheelFilename {
+    pub distribution: String,
+    pub version: String,
+    pub build_tag: Option<String>,
+    pub python_tag: String,
+    pub abi_tag: String,
+    pub platform_tag: String,
+}
+
+impl WheelFilename {
+    /// Parses a `.whl` filename.
+    ///
+    /// # Example
+    /// ```
+    /// let wheel = WheelFilename::parse("numpy-1.26.4-cp312-cp312-manylinux_2_17_x86_64.whl").unwrap();
+    /// ```
+    pub fn parse(filename: &str) -> Result<Self, String> {
+        let stem = filename
+            .strip_suffix(".whl")
+            .ok_or_else(|| format!("{} is not a wheel filename", filename))?;
+
+        // A distribution's name and version are normalized to never contain
+        // a `-`, so splitting on it is unambiguous.
+        match stem.split('-').collect::<Vec<_>>().as_slice() {
+            [distribution, version, python_tag, abi_tag, platform_tag] => Ok(Self {
+                distribution: distribution.to_string(),
+                version: version.to_string(),
+                build_tag: None,
+                python_tag: python_tag.to_string(),
+                abi_tag: abi_tag.to_string(),
+                platform_tag: platform_tag.to_string(),
+            }),
+            [distribution, version, build_tag, python_tag, abi_tag, platform_tag] => Ok(Self {
+                distribution: distribution.to_string(),
+                version: version.to_string(),
+                build_tag: Some(build_tag.to_string()),
+                python_tag: python_tag.to_string(),
+                abi_tag: abi_tag.to_string(),
+                platform_tag: platform_tag.to_string(),
+            }),
+            _ => Err(format!("{} does not match the wheel filename format", filename)),
+        }
+    }
+
+    /// This wheel's `(python_tag, abi_tag, platform_tag)` triple.
+    pub fn tag(&self) -> WheelTag {
+        (
+            self.python_tag.clone(),
+            self.abi_tag.clone(),
+            self.platform_tag.clone(),
+        )
+    }
+}
+
+/// The filename extensions a source distribution may be published under.
+/// None is a suffix of another, so the order they are tried in does not
+/// matter.
+const SDIST_EXTENSIONS: &[&str] = &[".tar.gz", ".tar.bz2", ".tar.xz", ".tar.Z", ".zip"];
+
+/// A parsed sdist filename: `{distribution}-{version}.{ext}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SdistFilename {
+    pub distribution: String,
+    pub version: String,
+}
+
+impl SdistFilename {
+    /// Parses a source distribution filename.
+    ///
+    /// # Example
+    /// ```
+    /// let sdist = SdistFilename::parse("numpy-1.26.4.tar.gz").unwrap();
+    /// ```
+    pub fn parse(filename: &str) -> Result<Self, String> {
+        let stem = SDIST_EXTENSIONS
+            .iter()
+            .find_map(|ext| filename.strip_suffix(ext))
+            .ok_or_else(|| format!("{} is not a recognized sdist filename", filename))?;
+
+        let (distribution, version) = stem
+            .rsplit_once('-')
+            .ok_or_else(|| format!("{} does not match the sdist filename format", filename))?;
+
+        Ok(Self {
+            distribution: distribution.to_string(),
+            version: version.to_string(),
+        })
+    }
+}
+
+/// A release artifact's filename, parsed as either a wheel or an sdist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DistributionFilename {
+    Wheel(WheelFilename),
+    Sdist(SdistFilename),
+}
+
+impl DistributionFilename {
+    /// Parses `filename` as a wheel if it ends in `.whl`, and as an sdist
+    /// otherwise.
+    pub fn parse(filename: &str) -> Result<Self, String> {
+        if filename.ends_with(".whl") {
+            WheelFilename::parse(filename).map(Self::Wheel)
+        } else {
+            SdistFilename::parse(filename).map(Self::Sdist)
+        }
+    }
+}
+
+/// Picks the best-matching release artifact for an interpreter that
+/// supports `compatible_tags`, which must be ordered most-specific first
+/// (as `packaging.tags.sys_tags()` produces them, ending in an `any`
+/// platform fallback).
+///
+/// Wheels are always preferred over sdists; among wheels, the one whose tag
+/// appears earliest in `compatible_tags` wins. Filenames that fail to parse,
+/// or wheels whose tag isn't supported at all, are skipped.
+pub fn select_best_distribution<'a>(
+    filenames: &'a [String],
+    compatible_tags: &[WheelTag],
+) -> Option<&'a str> {
+    let mut best: Option<(usize, &str)> = None;
+
+    for filename in filenames {
+        let parsed = match DistributionFilename::parse(filename) {
+            Ok(parsed) => parsed,
+            Err(_) => continue,
+        };
+
+        let rank = match parsed {
+            DistributionFilename::Wheel(wheel) => {
+                match compatible_tags.iter().position(|tag| *tag == wheel.tag()) {
+                    Some(rank) => rank,
+                    None => continue,
+                }
+            }
+            DistributionFilename::Sdist(_) => compatible_tags.len(),
+        };
+
+        if best.map_or(true, |(best_rank, _)| rank < best_rank) {
+            best = Some((rank, filename));
+        }
+    }
+
+    best.map(|(_, filename)| filename)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{select_best_distribution, DistributionFilename, SdistFilename, WheelFilename};
+
+    #[test]
+    fn check_wheel_filename_without_build_tag() {
+        let wheel = WheelFilename::parse("numpy-1.26.4-cp312-cp312-manylinux_2_17_x86_64.whl").unwrap();
+
+        assert_eq!(wheel.distribution, "numpy");
+        assert_eq!(wheel.version, "1.26.4");
+        assert_eq!(wheel.build_tag, None);
+        assert_eq!(wheel.python_tag, "cp312");
+        assert_eq!(wheel.abi_tag, "cp312");
+        assert_eq!(wheel.platform_tag, "manylinux_2_17_x86_64");
+    }
+
+    #[test]
+    fn check_wheel_filename_with_build_tag() {
+        let wheel = WheelFilename::parse("foo-1.0-1-py3-none-any.whl").unwrap();
+
+        assert_eq!(wheel.distribution, "foo");
+        assert_eq!(wheel.version, "1.0");
+        assert_eq!(wheel.build_tag, Some("1".to_string()));
+        assert_eq!(wheel.python_tag, "py3");
+        assert_eq!(wheel.abi_tag, "none");
+        assert_eq!(wheel.platform_tag, "any");
+    }
+
+    #[test]
+    fn check_sdist_filename() {
+        assert_eq!(
+            SdistFilename::parse("numpy-1.26.4.tar.gz").unwrap(),
+            SdistFilename {
+                distribution: "numpy".to_string(),
+                version: "1.26.4".to_string(),
+            }
+        );
+        assert_eq!(
+            SdistFilename::parse("numpy-1.26.4.zip").unwrap(),
+            SdistFilename {
+                distribution: "numpy".to_string(),
+                version: "1.26.4".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn check_distribution_filename_dispatches_on_extension() {
+        assert!(matches!(
+            DistributionFilename::parse("numpy-1.26.4-cp312-cp312-manylinux_2_17_x86_64.whl"),
+            Ok(DistributionFilename::Wheel(_))
+        ));
+        assert!(matches!(
+            DistributionFilename::parse("numpy-1.26.4.tar.gz"),
+            Ok(DistributionFilename::Sdist(_))
+        ));
+    }
+
+    #[test]
+    fn check_unrecognized_filenames_are_rejected() {
+        assert!(WheelFilename::parse("numpy-1.0.1.dev3460.win32-py2.4.exe").is_err());
+        assert!(SdistFilename::parse("numpy-1.0.1.dev3460.win32-py2.4.exe").is_err());
+    }
+
+    #[test]
+    fn check_select_best_distribution_prefers_wheels_over_sdists() {
+        let filenames = vec![
+            "numpy-1.26.4.tar.gz".to_string(),
+            "numpy-1.26.4-cp312-cp312-manylinux_2_17_x86_64.whl".to_string(),
+        ];
+        let compatible_tags = vec![(
+            "cp312".to_string(),
+            "cp312".to_string(),
+            "manylinux_2_17_x86_64".to_string(),
+        )];
+
+        assert_eq!(
+            select_best_distribution(&filenames, &compatible_tags),
+            Some("numpy-1.26.4-cp312-cp312-manylinux_2_17_x86_64.whl")
+        );
+    }
+
+    #[test]
+    fn check_select_best_distribution_prefers_more_specific_platform_tags() {
+        let filenames = vec![
+            "foo-1.0-py3-none-any.whl".to_string(),
+            "foo-1.0-cp312-cp312-manylinux_2_17_x86_64.whl".to_string(),
+        ];
+        // Most-specific first, `any` last, as packaging.tags.sys_tags() orders them.
+        let compatible_tags = vec![
+            (
+                "cp312".to_string(),
+                "cp312".to_string(),
+                "manylinux_2_17_x86_64".to_string(),
+            ),
+            ("py3".to_string(), "none".to_string(), "any".to_string()),
+        ];
+
+        assert_eq!(
+            select_best_distribution(&filenames, &compatible_tags),
+            Some("foo-1.0-cp312-cp312-manylinux_2_17_x86_64.whl")
+        );
+    }
+
+    #[test]
+    fn check_select_best_distribution_skips_incompatible_wheels() {
+        let filenames = vec!["foo-1.0-cp312-cp312-manylinux_2_17_x86_64.whl".to_string()];
+        let compatible_tags = vec![("py3".to_string(), "none".to_string(), "any".to_string())];
+
+        assert_eq!(select_best_distribution(&filenames, &compatible_tags), None);
+    }
+}
@@ -1,10 +1,19 @@
 use std::collections::HashMap;
 
+use anyhow::{Context, Result};
 use clap::{AppSettings, Parser};
 use serde::{Deserialize, Serialize};
 use serde_json;
-use pyver;
-use pyver::PackageVersion;
+
+mod distribution_filename;
+mod package_version;
+mod pypi;
+mod requirements;
+mod resolver;
+mod version;
+use package_version::PackageVersion;
+use requirements::{MarkerEnvironment, PyRequirements};
+use resolver::resolve;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct PackageData {
@@ -115,15 +124,22 @@ struct PackageInfo {
 #[clap(global_setting = AppSettings::DeriveDisplayOrder)]
 enum Opt {
     /// Install packages.
-    Install {},
+    Install {
+        /// Path or URL of the requirements file to resolve and install.
+        #[clap(short = 'r', long = "requirement")]
+        requirement: String,
+        #[clap(short = 'i', long = "index", default_value = "https://pypi.org/")]
+        index: String,
+    },
     /// Download packages.
     Download {
         #[clap(short = 'n', long = "name")]
         name: String,
         #[clap(short = 'i', long = "index", default_value = "https://pypi.org/")]
         index: String,
+        /// Exact version to download. Defaults to the latest non-pre-release.
         #[clap(short = 'v', long = "package-version")]
-        package_version: String,
+        package_version: Option<String>,
     },
     /// Uninstall packages.
     Uninstall {},
@@ -158,8 +174,8 @@ enum Opt {
 async fn download_package(
     package_name: String,
     package_index: &str,
-    package_vrsion: &str,
-) -> Result<(), reqwest::Error> {
+    package_version: Option<&str>,
+) -> Result<()> {
     // "https://pypi.org/pypi/sgai/json"
     let a = format!("{}pypi/{}/json", package_index, package_name);
     println!("{}", a);
@@ -169,29 +185,62 @@ async fn download_package(
         .await?
         .json()
         .await?;
-    let mut rels =
-        Vec::from_iter(body.releases.keys().cloned());
-    rels.sort();
-    rels.reverse();
-    println!("{:?}", &rels);
+    // Parse every release key as a real PEP 440 version so the list is
+    // sorted newest-first by actual version ordering rather than by string
+    // comparison; releases that fail to parse are skipped instead of
+    // panicking.
+    let mut parsed_releases: Vec<(PackageVersion, &String)> = body
+        .releases
+        .keys()
+        .filter_map(|raw| PackageVersion::new(raw).ok().map(|version| (version, raw)))
+        .collect();
+    parsed_releases.sort_by(|(a, _), (b, _)| b.cmp(a));
+    println!(
+        "{:?}",
+        parsed_releases.iter().map(|(_, raw)| *raw).collect::<Vec<_>>()
+    );
 
-    // println!("{:#?}", body);
+    // Default to the latest non-pre-release; fall back to the latest
+    // release at all (including a pre-release) if every parsed release is
+    // one, rather than finding nothing to download.
+    let selected_version = match package_version {
+        Some(requested) => requested.to_string(),
+        None => parsed_releases
+            .iter()
+            .find(|(version, _)| !version.is_prerelease())
+            .or_else(|| parsed_releases.first())
+            .map(|(_, raw)| (*raw).clone())
+            .with_context(|| format!("{} has no releases to download", package_name))?,
+    };
 
-    // Error: reqwest::Error { kind: Decode, source: Error("invalid type: null, expected a string", line: 1, co
-    // lumn: 2914) }
     let dow = body
         .releases
-        .get(&package_vrsion as &str).unwrap();
-    // let dow = dow;
-    // .map(|p| &p.url);
-    // println!("{:?}", dow);
-    // let resp = reqwest::get(&dow.get(0)).await?.bytes().await?;
-    // std::fs::write(&dow.filename, resp).unwrap();
+        .get(&selected_version as &str)
+        .with_context(|| format!("{} has no release {}", package_name, selected_version))?;
+    println!("{:?}", dow);
+    // let resp = reqwest::get(&dow.get(0).url).await?.bytes().await?;
+    // std::fs::write(&dow.get(0).filename, resp).unwrap();
+    Ok(())
+}
+
+/// Resolves `requirement`'s dependencies against `package_index` and prints
+/// the resulting pin for each package, one per line, in the
+/// `name==version` shape a lockfile would use.
+fn install_packages(requirement: &str, package_index: &str) -> Result<()> {
+    let requirements = PyRequirements::new(requirement)
+        .map_err(|err| anyhow::anyhow!("unable to parse {}: {}", requirement, err))?;
+    let env = MarkerEnvironment::default();
+
+    let pinned = resolve(&requirements.requirements, package_index, &env)?;
+    for package in pinned {
+        println!("{}=={}", package.name, package.version);
+    }
+
     Ok(())
 }
 
 #[tokio::main]
-async fn main() -> Result<(), reqwest::Error> {
+async fn main() -> Result<()> {
     let opt = Opt::parse();
     match opt {
         Opt::Download {
@@ -199,7 +248,10 @@ async fn main() -> Result<(), reqwest::Error> {
             index,
             package_version,
         } => {
-            download_package(name, &index, &package_version).await?;
+            download_package(name, &index, package_version.as_deref()).await?;
+        }
+        Opt::Install { requirement, index } => {
+            install_packages(&requirement, &index)?;
         }
         _ => todo!(),
     }
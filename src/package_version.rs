@@ -86,13 +86,21 @@ static VALIDATION_REGEX: &str = pomsky!(
 );
 
 /// # Pep-440 Developmental release identifier
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, PartialOrd)]
 pub struct DevHead {
     dev_num: Option<u32>,
 }
 
+impl DevHead {
+    /// Renders this dev release in its canonical form: `.devN`, with an
+    /// implicit `0` when no number was given.
+    fn normalize(&self) -> String {
+        format!(".dev{}", self.dev_num.unwrap_or(0))
+    }
+}
+
 /// Pep-440 Post-Release Identifier Keyword
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub enum PostHead {
     Post,
     Rev,
@@ -105,12 +113,21 @@ impl PartialOrd for PostHead {
 }
 
 /// # Pep-440 Post-Release identifier
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub struct PostHeader {
     pub post_head: Option<PostHead>,
     pub post_num: Option<u32>,
 }
 
+impl PostHeader {
+    /// Renders this post-release in its canonical form: `.postN` (per PEP
+    /// 440, the legacy `rev`/`r` spellings also normalize to `post`), with an
+    /// implicit `0` when no number was given.
+    fn normalize(&self) -> String {
+        format!(".post{}", self.post_num.unwrap_or(0))
+    }
+}
+
 impl PartialOrd for PostHeader {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         if self.post_num == other.post_num {
@@ -132,41 +149,185 @@ impl PartialOrd for PostHeader {
 }
 
 /// # Pep-440 Pre-Release identifier
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, PartialOrd)]
+///
+/// Variants are declared in PEP 440's ordering (`a < b < rc`), since
+/// `#[derive(PartialOrd)]` compares different variants by declaration
+/// order. PEP 440 treats `pre`/`preview`/`c`/`rc` as spellings of the same
+/// release-candidate level, so there is a single `ReleaseCanidate` variant
+/// rather than a separate `Preview` one — keeping them apart made
+/// `1.0pre1`/`1.0rc1` normalize to the same string yet compare unequal.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, PartialOrd)]
 pub enum PreHeader {
-    /// Present in versions like 1.1beta1 or 1.0b1 both are represented the same way
-    /// ```
-    /// PreHeader::Beta(Some(1))
-    /// ```
-    Beta(Option<u32>),
     /// Present in versions like 1.0alpha2 or 1.0a2 both are represented the same way
     /// ```
     /// PreHeader::Alpha(Some(2))
     /// ```
     Alpha(Option<u32>),
-    /// Present in versions like 1.1pre3
+    /// Present in versions like 1.1beta1 or 1.0b1 both are represented the same way
     /// ```
-    /// PreHeader::Preview(Some(3))
+    /// PreHeader::Beta(Some(1))
     /// ```
-    Preview(Option<u32>),
-    /// Present in versions like 1.1-rc-4 or 1.1c-4
+    Beta(Option<u32>),
+    /// Present in versions like 1.1pre3, 1.1preview3, 1.1c4, or 1.1-rc-4, all
+    /// represented the same way
     /// ```
     /// PreHeader::ReleaseCanidate(Some(4))
     /// ```
     ReleaseCanidate(Option<u32>),
 }
 
-/// Pep-440 Release numbers
-#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, PartialOrd)]
+impl PreHeader {
+    /// Renders this pre-release in its canonical spelling: `a`, `b`, or `rc`
+    /// (per PEP 440, `alpha`/`beta`/`c`/`pre`/`preview` all normalize to one
+    /// of those three labels), followed by its number, or an implicit `0`
+    /// when none was given.
+    fn normalize(&self) -> String {
+        let (label, num) = match self {
+            Self::Alpha(num) => ("a", num),
+            Self::Beta(num) => ("b", num),
+            Self::ReleaseCanidate(num) => ("rc", num),
+        };
+        format!("{}{}", label, num.unwrap_or(0))
+    }
+}
+
+/// Pep-440 Release numbers: one or more non-negative integer segments, e.g.
+/// `1.0.15` or `2013.10`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReleaseHeader {
-    /// Major release such as 1.0 or breaking changes
-    pub major: u32,
-    /// Minor release Such as new functionality
-    pub minor: u32,
+    pub segments: Vec<u32>,
+}
+
+impl PartialEq for ReleaseHeader {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl Eq for ReleaseHeader {}
+
+impl PartialOrd for ReleaseHeader {
+    /// Compares segment-by-segment, zero-padding the shorter release out to
+    /// the longer one's length, so `1.0` == `1.0.0`.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let len = self.segments.len().max(other.segments.len());
+
+        for idx in 0..len {
+            let a = self.segments.get(idx).copied().unwrap_or(0);
+            let b = other.segments.get(idx).copied().unwrap_or(0);
+            match a.cmp(&b) {
+                Ordering::Equal => continue,
+                ordering => return Some(ordering),
+            }
+        }
+
+        Some(Ordering::Equal)
+    }
+}
+
+/// A single segment of a Pep-440 local version identifier.
+///
+/// Per PEP 440, numeric segments always sort above alphabetic ones, and are
+/// compared numerically; alphabetic segments are compared case-insensitively.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub(crate) enum LocalSegment {
+    Numeric(u64),
+    Alpha(String),
+}
+
+impl LocalSegment {
+    fn parse(raw: &str) -> Self {
+        match raw.parse::<u64>() {
+            Ok(n) => Self::Numeric(n),
+            Err(_) => Self::Alpha(raw.to_lowercase()),
+        }
+    }
+
+    /// Renders this segment in its canonical form (lowercase, no leading zeroes).
+    fn normalize(&self) -> String {
+        match self {
+            Self::Numeric(n) => n.to_string(),
+            Self::Alpha(s) => s.clone(),
+        }
+    }
+}
+
+impl PartialOrd for LocalSegment {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(match (self, other) {
+            (Self::Numeric(a), Self::Numeric(b)) => a.cmp(b),
+            (Self::Alpha(a), Self::Alpha(b)) => a.cmp(b),
+            // A numeric segment always outranks an alphabetic one.
+            (Self::Numeric(_), Self::Alpha(_)) => Ordering::Greater,
+            (Self::Alpha(_), Self::Numeric(_)) => Ordering::Less,
+        })
+    }
+}
+
+/// Splits a local version identifier (e.g. `abc.5`) into its segments on
+/// `.`, `-`, and `_`.
+fn parse_local_segments(local: &str) -> Vec<LocalSegment> {
+    local
+        .split(['.', '-', '_'])
+        .map(LocalSegment::parse)
+        .collect()
+}
+
+/// Compares two parsed local version identifiers per PEP 440: segment by
+/// segment, with an absent local version (an empty segment list) sorting
+/// below a present one, and a longer identifier sorting above a shorter one
+/// once their shared segments match — both simply fall out of comparing the
+/// segment lists lexicographically.
+fn compare_local(a: &[LocalSegment], b: &[LocalSegment]) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+/// Compares two optional developmental-release markers. An absent `dev`
+/// release outranks a present one, since `.devN` sorts before the release it
+/// precedes.
+fn compare_dev(a: &Option<DevHead>, b: &Option<DevHead>) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+    }
+}
+
+/// The pre-release component of a version, ranked for comparison as PEP 440
+/// prescribes: a pure `.devN` release (no pre, no post) sorts below every
+/// pre-release, while a version with no pre-release at all (a final release
+/// or a post-release) sorts above every pre-release.
+enum PreRank<'a> {
+    DevOnly,
+    Release(&'a PreHeader),
+    Final,
+}
+
+fn pre_rank(version: &PackageVersion) -> PreRank {
+    match &version.pre {
+        Some(pre) => PreRank::Release(pre),
+        None if version.post.is_none() && version.dev.is_some() => PreRank::DevOnly,
+        None => PreRank::Final,
+    }
+}
+
+impl PreRank<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::DevOnly, Self::DevOnly) => Some(Ordering::Equal),
+            (Self::Final, Self::Final) => Some(Ordering::Equal),
+            (Self::DevOnly, _) => Some(Ordering::Less),
+            (_, Self::DevOnly) => Some(Ordering::Greater),
+            (Self::Final, _) => Some(Ordering::Greater),
+            (_, Self::Final) => Some(Ordering::Less),
+            (Self::Release(a), Self::Release(b)) => a.partial_cmp(b),
+        }
+    }
 }
 
 /// Pep-440 Compliant versioning system
-/// 
+///
 /// This struct is sorted so that PartialOrd
 /// corretly interpets priority
 ///
@@ -176,10 +337,10 @@ pub struct ReleaseHeader {
 /// ```
 /// let _ = PackageVersion::new("v1.0");
 /// ```
-#[derive(Derivative, Debug, Serialize, Deserialize)]
-#[derivative(PartialOrd, PartialEq)]
+#[derive(Derivative, Debug, Clone, Serialize, Deserialize)]
+#[derivative(PartialEq)]
 pub struct PackageVersion {
-    #[derivative(PartialOrd = "ignore", PartialEq = "ignore")]
+    #[derivative(PartialEq = "ignore")]
     pub original: String,
 
     /// # Pep-440 Local version identifier
@@ -194,9 +355,14 @@ pub struct PackageVersion {
     ///  ['a'-'z' '0'-'9']+
     ///  ((["-" "_" "."] ['a'-'z' '0'-'9']+)+)?
     /// ```
-    #[derivative(PartialOrd = "ignore", PartialEq = "ignore")]
     pub local: Option<String>,
 
+    /// Parsed form of `local`, split into [`LocalSegment`]s at parse time so
+    /// ordering doesn't need to re-split the local version string on every
+    /// comparison. Empty when `local` is `None`.
+    #[derivative(PartialEq = "ignore")]
+    pub(crate) local_segments: Vec<LocalSegment>,
+
     /// # Pep-440 Developmental release identifier
     pub dev: Option<DevHead>,
 
@@ -238,21 +404,13 @@ impl PackageVersion {
         };
 
         let release: ReleaseHeader = match version_match.name("release") {
-            Some(v) => {
-                // Does Release String contain minor version
-                if v.as_str().contains('.') {
-                    let split: Vec<&str> = v.as_str().split('.').into_iter().collect();
-                    ReleaseHeader {
-                        major: split[0].parse::<u32>()?,
-                        minor: split[1].parse::<u32>()?,
-                    }
-                } else {
-                    ReleaseHeader {
-                        major: v.as_str().parse::<u32>()?,
-                        minor: 0,
-                    }
-                }
-            }
+            Some(v) => ReleaseHeader {
+                segments: v
+                    .as_str()
+                    .split('.')
+                    .map(str::parse::<u32>)
+                    .collect::<std::result::Result<Vec<_>, _>>()?,
+            },
             // There always has to be at least a major version
             None => anyhow::bail!("Failed to decode version {}", version),
         };
@@ -273,8 +431,8 @@ impl PackageVersion {
                     "b" => Some(PreHeader::Beta(pre_n)),
                     "rc" => Some(PreHeader::ReleaseCanidate(pre_n)),
                     "c" => Some(PreHeader::ReleaseCanidate(pre_n)),
-                    "preview" => Some(PreHeader::Preview(pre_n)),
-                    "pre" => Some(PreHeader::Preview(pre_n)),
+                    "preview" => Some(PreHeader::ReleaseCanidate(pre_n)),
+                    "pre" => Some(PreHeader::ReleaseCanidate(pre_n)),
                     _ => None,
                 }
             }
@@ -325,6 +483,7 @@ impl PackageVersion {
 
         let local: Option<String> =
             version_match.name("local").map(|v| v.as_str().to_string());
+        let local_segments = local.as_deref().map(parse_local_segments).unwrap_or_default();
 
         Ok(Self {
             original: version.to_string(),
@@ -334,13 +493,126 @@ impl PackageVersion {
             post,
             dev,
             local,
+            local_segments,
         })
     }
+
+    /// Renders this version in its canonical PEP 440 form:
+    /// `[N!]N(.N)*[{a|b|rc}N][.postN][.devN][+local]`, with pre/post
+    /// spellings normalized and the local version label lowercased.
+    ///
+    /// Unlike [`original`](Self::original), this drops whatever spelling and
+    /// separators the input used (e.g. a leading `v`, `alpha` instead of `a`,
+    /// `-` instead of `.` in the local label, an explicit `0!` epoch), so two
+    /// versions that differ only in spelling normalize to the same string.
+    /// Release segments are rendered as given, so `1.0` and `1.0.0`, which
+    /// compare equal, normalize differently.
+    pub fn normalize(&self) -> String {
+        let mut out = String::new();
+
+        if let Some(epoch) = self.epoch.filter(|&epoch| epoch != 0) {
+            out.push_str(&format!("{}!", epoch));
+        }
+
+        out.push_str(
+            &self
+                .release
+                .segments
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join("."),
+        );
+
+        if let Some(pre) = &self.pre {
+            out.push_str(&pre.normalize());
+        }
+
+        if let Some(post) = &self.post {
+            out.push_str(&post.normalize());
+        }
+
+        if let Some(dev) = &self.dev {
+            out.push_str(&dev.normalize());
+        }
+
+        if !self.local_segments.is_empty() {
+            out.push('+');
+            out.push_str(
+                &self
+                    .local_segments
+                    .iter()
+                    .map(LocalSegment::normalize)
+                    .collect::<Vec<_>>()
+                    .join("."),
+            );
+        }
+
+        out
+    }
+
+    /// Returns whether this version carries a pre-release or dev-release
+    /// segment (e.g. `1.0a1` or `1.0.dev1`), the key accessor callers use to
+    /// exclude non-final versions when picking a "latest" version from a
+    /// sorted release list.
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+}
+
+impl Eq for PackageVersion {}
+
+impl Ord for PackageVersion {
+    /// `PartialOrd::partial_cmp` is total for every [`PackageVersion`] (every
+    /// comparison step below resolves to a concrete [`Ordering`]), so this
+    /// just unwraps it to give callers a total order to sort release lists
+    /// with.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).expect("PackageVersion ordering is total")
+    }
+}
+
+impl PartialOrd for PackageVersion {
+    /// Orders versions per PEP 440: epoch, then release, then the
+    /// pre/post/dev modifiers (a `.devN`-only release sorts lowest, a final
+    /// or post release sorts highest, everything else by pre-release
+    /// ordinal), then the local version identifier.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let epoch_cmp = self.epoch.unwrap_or(0).cmp(&other.epoch.unwrap_or(0));
+        if epoch_cmp != Ordering::Equal {
+            return Some(epoch_cmp);
+        }
+
+        let release_cmp = self.release.partial_cmp(&other.release)?;
+        if release_cmp != Ordering::Equal {
+            return Some(release_cmp);
+        }
+
+        let pre_cmp = pre_rank(self).partial_cmp(&pre_rank(other))?;
+        if pre_cmp != Ordering::Equal {
+            return Some(pre_cmp);
+        }
+
+        let post_cmp = self.post.partial_cmp(&other.post)?;
+        if post_cmp != Ordering::Equal {
+            return Some(post_cmp);
+        }
+
+        let dev_cmp = compare_dev(&self.dev, &other.dev);
+        if dev_cmp != Ordering::Equal {
+            return Some(dev_cmp);
+        }
+
+        Some(compare_local(&self.local_segments, &other.local_segments))
+    }
 }
 
 impl fmt::Display for PackageVersion {
+    /// Round-trips through [`normalize`](Self::normalize) rather than
+    /// `original`, so equal versions with different spellings (`v1.0` vs
+    /// `1.0`, `1.0alpha1` vs `1.0a1`) display identically.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.original)
+        write!(f, "{}", self.normalize())
     }
 }
 
@@ -353,6 +625,7 @@ mod tests {
     use anyhow::Result;
 
     use super::DevHead;
+    use super::LocalSegment;
     use super::PostHead;
     use super::PostHeader;
     use super::PreHeader;
@@ -384,34 +657,60 @@ mod tests {
     #[test]
     fn check_release_ordering() -> Result<()> {
         check_a_greater(
-            ReleaseHeader { major: 1, minor: 0 },
-            ReleaseHeader { major: 0, minor: 0 },
+            ReleaseHeader { segments: vec![1, 0] },
+            ReleaseHeader { segments: vec![0, 0] },
         )?;
         check_a_greater(
-            ReleaseHeader { major: 1, minor: 1 },
-            ReleaseHeader { major: 1, minor: 0 },
+            ReleaseHeader { segments: vec![1, 1] },
+            ReleaseHeader { segments: vec![1, 0] },
         )?;
         check_a_greater(
-            ReleaseHeader { major: 2, minor: 1 },
-            ReleaseHeader {
-                major: 1,
-                minor: 52,
-            },
+            ReleaseHeader { segments: vec![2, 1] },
+            ReleaseHeader { segments: vec![1, 52] },
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn check_release_zero_pads_shorter_segments() -> Result<()> {
+        // PEP 440: `1.0` == `1.0.0`, and a longer release only outranks a
+        // shorter one when its extra segments are non-zero.
+        assert_eq!(
+            ReleaseHeader { segments: vec![1, 0] },
+            ReleaseHeader { segments: vec![1, 0, 0] }
+        );
+        check_a_greater(
+            ReleaseHeader { segments: vec![1, 0, 1] },
+            ReleaseHeader { segments: vec![1, 0] },
         )?;
         Ok(())
     }
 
+    #[test]
+    fn check_long_release_segments_are_preserved() -> Result<()> {
+        assert_eq!(
+            PackageVersion::new("1.0.15")?.release,
+            ReleaseHeader { segments: vec![1, 0, 15] }
+        );
+        assert_eq!(
+            PackageVersion::new("2013.10.04")?.release,
+            ReleaseHeader {
+                segments: vec![2013, 10, 4]
+            }
+        );
+        check_a_greater(PackageVersion::new("1.2.3.4")?, PackageVersion::new("1.2.3")?)?;
+        Ok(())
+    }
+
     #[test]
     fn check_pre_ordering() -> Result<()> {
-        check_a_greater(PreHeader::ReleaseCanidate(None), PreHeader::Preview(None))?;
-        check_a_greater(PreHeader::Preview(None), PreHeader::Alpha(None))?;
-        check_a_greater(PreHeader::Alpha(None), PreHeader::Beta(None))?;
+        check_a_greater(PreHeader::ReleaseCanidate(None), PreHeader::Alpha(None))?;
+        check_a_greater(PreHeader::Beta(None), PreHeader::Alpha(None))?;
 
         check_a_greater(
             PreHeader::ReleaseCanidate(Some(2)),
             PreHeader::ReleaseCanidate(Some(1)),
         )?;
-        check_a_greater(PreHeader::Preview(Some(50)), PreHeader::Preview(Some(3)))?;
         check_a_greater(PreHeader::Alpha(Some(504)), PreHeader::Alpha(Some(0)))?;
         check_a_greater(PreHeader::Beta(Some(1234)), PreHeader::Beta(Some(1)))?;
 
@@ -419,6 +718,9 @@ mod tests {
             PreHeader::ReleaseCanidate(Some(1)),
             PreHeader::Beta(Some(45067885)),
         )?;
+
+        // `pre`/`preview`/`c`/`rc` are all the same release-candidate level.
+        assert_eq!(PreHeader::ReleaseCanidate(Some(3)), PreHeader::ReleaseCanidate(Some(3)));
         Ok(())
     }
 
@@ -523,4 +825,104 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn check_dev_pre_post_ordering() -> Result<()> {
+        // 1.0.dev1 < 1.0a1 < 1.0rc1 < 1.0 < 1.0.post1
+        check_a_greater(PackageVersion::new("1.0a1")?, PackageVersion::new("1.0.dev1")?)?;
+        check_a_greater(PackageVersion::new("1.0rc1")?, PackageVersion::new("1.0a1")?)?;
+        check_a_greater(PackageVersion::new("1.0")?, PackageVersion::new("1.0rc1")?)?;
+        check_a_greater(PackageVersion::new("1.0.post1")?, PackageVersion::new("1.0")?)?;
+        Ok(())
+    }
+
+    #[test]
+    fn check_local_version_ordering() -> Result<()> {
+        // A version with a local identifier sorts above the same version
+        // without one, and local identifiers compare segment-by-segment.
+        check_a_greater(PackageVersion::new("1.0+abc.5")?, PackageVersion::new("1.0")?)?;
+        check_a_greater(
+            PackageVersion::new("1.0+abc.7")?,
+            PackageVersion::new("1.0+abc.5")?,
+        )?;
+        check_a_greater(
+            PackageVersion::new("1.0+abc.1.2")?,
+            PackageVersion::new("1.0+abc.1")?,
+        )?;
+        // Numeric segments always outrank alphabetic ones.
+        check_a_greater(PackageVersion::new("1.0+1")?, PackageVersion::new("1.0+abc")?)?;
+        Ok(())
+    }
+
+    #[test]
+    fn check_normalize_canonicalizes_spelling() -> Result<()> {
+        assert_eq!(PackageVersion::new("v1.0")?.normalize(), "1.0");
+        assert_eq!(PackageVersion::new("1.0alpha1")?.normalize(), "1.0a1");
+        assert_eq!(PackageVersion::new("1.0-preview2")?.normalize(), "1.0rc2");
+        assert_eq!(PackageVersion::new("1.0-rev2")?.normalize(), "1.0.post2");
+        assert_eq!(PackageVersion::new("1!1.0.dev3")?.normalize(), "1!1.0.dev3");
+        assert_eq!(PackageVersion::new("1.0+abc-5")?.normalize(), "1.0+abc.5");
+        Ok(())
+    }
+
+    #[test]
+    fn check_pre_and_preview_spellings_compare_equal() -> Result<()> {
+        // `pre`/`preview`/`c`/`rc` are all the same release-candidate level,
+        // so versions spelled with different synonyms must compare equal,
+        // not just normalize to the same string.
+        assert_eq!(
+            PackageVersion::new("1.0pre1")?,
+            PackageVersion::new("1.0rc1")?
+        );
+        assert_eq!(
+            PackageVersion::new("1.0preview1")?,
+            PackageVersion::new("1.0c1")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn check_display_round_trips_through_normalize() -> Result<()> {
+        assert_eq!(PackageVersion::new("v1.0")?.to_string(), "1.0");
+        assert_eq!(
+            PackageVersion::new("1.0")?.to_string(),
+            PackageVersion::new("v1.0")?.to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn check_sorts_a_release_list_via_ord() -> Result<()> {
+        let mut releases = vec![
+            PackageVersion::new("1.0")?,
+            PackageVersion::new("2.0")?,
+            PackageVersion::new("1.5")?,
+            PackageVersion::new("1.0a1")?,
+        ];
+        releases.sort();
+
+        assert_eq!(
+            releases.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["1.0a1", "1.0", "1.5", "2.0"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn check_is_prerelease() -> Result<()> {
+        assert!(PackageVersion::new("1.0a1")?.is_prerelease());
+        assert!(PackageVersion::new("1.0.dev1")?.is_prerelease());
+        assert!(!PackageVersion::new("1.0")?.is_prerelease());
+        Ok(())
+    }
+
+    #[test]
+    fn check_local_segments_are_precomputed() -> Result<()> {
+        assert_eq!(PackageVersion::new("1.0")?.local_segments, Vec::new());
+        assert_eq!(
+            PackageVersion::new("1.0+abc.5")?.local_segments,
+            vec![LocalSegment::Alpha("abc".to_string()), LocalSegment::Numeric(5)]
+        );
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -87,6 +87,27 @@ where
     Ok(decoded_json)
 }
 
+/// Like [`request_package_info`], but scoped to a single release, so
+/// `info.requires_dist` and `urls` describe that release rather than
+/// whichever one PyPI currently considers latest.
+pub fn request_package_version_info<T>(
+    package_name: T,
+    version: T,
+    package_index: T,
+) -> Result<PyPIData, reqwest::Error>
+where
+    T: ToString + Display,
+{
+    let path = format!("{}/pypi/{}/{}/json", package_index, package_name, version);
+
+    info!("Requesting data from {}", path);
+    let resp: reqwest::blocking::Response = reqwest::blocking::get(path)?;
+
+    let decoded_json: PyPIData = resp.json()?;
+
+    Ok(decoded_json)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::pypi::request_package_info;
@@ -0,0 +1,406 @@
+//! PEP 508 environment marker parsing and evaluation, e.g. the
+//! `python_version < "3.8" and sys_platform == "win32"` half of
+//! `requests==2.0 ; python_version < "3.8" and sys_platform == "win32"`.
+
+use anyhow::{bail, Result};
+
+/// The marker variables defined by PEP 508 that this crate understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkerVariable {
+    PythonVersion,
+    PythonFullVersion,
+    SysPlatform,
+    OsName,
+    PlatformMachine,
+    ImplementationName,
+    Extra,
+}
+
+impl MarkerVariable {
+    fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "python_version" => Some(Self::PythonVersion),
+            "python_full_version" => Some(Self::PythonFullVersion),
+            "sys_platform" => Some(Self::SysPlatform),
+            "os_name" => Some(Self::OsName),
+            "platform_machine" => Some(Self::PlatformMachine),
+            "implementation_name" => Some(Self::ImplementationName),
+            "extra" => Some(Self::Extra),
+            _ => None,
+        }
+    }
+
+    fn resolve(self, env: &MarkerEnvironment) -> Option<String> {
+        match self {
+            Self::PythonVersion => Some(env.python_version.clone()),
+            Self::PythonFullVersion => Some(env.python_full_version.clone()),
+            Self::SysPlatform => Some(env.sys_platform.clone()),
+            Self::OsName => Some(env.os_name.clone()),
+            Self::PlatformMachine => Some(env.platform_machine.clone()),
+            Self::ImplementationName => Some(env.implementation_name.clone()),
+            Self::Extra => env.extra.clone(),
+        }
+    }
+}
+
+/// The comparison operators PEP 508 markers may use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkerOperator {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    /// Substring test, e.g. `"win" in sys_platform`.
+    In,
+    /// Negated substring test, e.g. `"win" not in sys_platform`.
+    NotIn,
+}
+
+impl MarkerOperator {
+    /// Matches the single-token operators (`==`, `!=`, `<`, `<=`, `>`,
+    /// `>=`). `in`/`not in` are two words and handled separately by the
+    /// parser, since `not` needs a lookahead to tell it apart from a bare
+    /// identifier.
+    fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "==" => Some(Self::Eq),
+            "!=" => Some(Self::NotEq),
+            "<" => Some(Self::Lt),
+            "<=" => Some(Self::LtEq),
+            ">" => Some(Self::Gt),
+            ">=" => Some(Self::GtEq),
+            _ => None,
+        }
+    }
+
+    /// Compares `actual` against `expected`. `In`/`NotIn` test whether
+    /// `expected` is a substring of `actual`. The remaining operators treat
+    /// dotted, all-numeric strings (as `python_version` always is)
+    /// component-wise; anything else falls back to a plain string compare.
+    fn compare(self, actual: &str, expected: &str) -> bool {
+        if matches!(self, Self::In | Self::NotIn) {
+            let contains = actual.contains(expected);
+            return if matches!(self, Self::In) { contains } else { !contains };
+        }
+
+        let ordering = match (parse_dotted(actual), parse_dotted(expected)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => actual.cmp(expected),
+        };
+
+        match self {
+            Self::Eq => ordering.is_eq(),
+            Self::NotEq => ordering.is_ne(),
+            Self::Lt => ordering.is_lt(),
+            Self::LtEq => ordering.is_le(),
+            Self::Gt => ordering.is_gt(),
+            Self::GtEq => ordering.is_ge(),
+            Self::In | Self::NotIn => unreachable!("handled above"),
+        }
+    }
+}
+
+fn parse_dotted(value: &str) -> Option<Vec<u32>> {
+    value.split('.').map(|part| part.parse::<u32>().ok()).collect()
+}
+
+/// The interpreter/platform values a marker expression is evaluated
+/// against.
+#[derive(Debug, Default, Clone)]
+pub struct MarkerEnvironment {
+    pub python_version: String,
+    pub python_full_version: String,
+    pub sys_platform: String,
+    pub os_name: String,
+    pub platform_machine: String,
+    pub implementation_name: String,
+    /// The extra currently being resolved for, if any. A bare `extra`
+    /// marker with no extras requested never matches.
+    pub extra: Option<String>,
+}
+
+/// A parsed PEP 508 marker expression.
+#[derive(Debug, Clone)]
+pub enum MarkerExpr {
+    Comparison {
+        variable: MarkerVariable,
+        operator: MarkerOperator,
+        value: String,
+    },
+    And(Box<MarkerExpr>, Box<MarkerExpr>),
+    Or(Box<MarkerExpr>, Box<MarkerExpr>),
+}
+
+impl MarkerExpr {
+    /// Evaluates this expression against `env`.
+    pub fn evaluate(&self, env: &MarkerEnvironment) -> bool {
+        match self {
+            Self::And(lhs, rhs) => lhs.evaluate(env) && rhs.evaluate(env),
+            Self::Or(lhs, rhs) => lhs.evaluate(env) || rhs.evaluate(env),
+            Self::Comparison {
+                variable,
+                operator,
+                value,
+            } => match variable.resolve(env) {
+                Some(actual) => operator.compare(&actual, value),
+                None => false,
+            },
+        }
+    }
+}
+
+/// Parses a PEP 508 marker expression, e.g.
+/// `python_version < "3.8" and sys_platform == "win32"`.
+///
+/// # Example
+/// ```
+/// let marker = parse_marker(r#"python_version < "3.8""#).unwrap();
+/// ```
+pub fn parse_marker(input: &str) -> Result<MarkerExpr> {
+    let tokens = tokenize(input)?;
+    let mut pos = 0;
+    let expr = parse_or(&tokens, &mut pos)?;
+    if pos != tokens.len() {
+        bail!("unexpected trailing input in marker: {}", input);
+    }
+    Ok(expr)
+}
+
+/// Splits a marker expression into identifiers, operators, parens, and
+/// quoted literals (re-wrapped in `"`, regardless of their original quote
+/// character, so the parser can tell them apart from bare identifiers).
+fn tokenize(input: &str) -> Result<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&ch) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        if ch == '(' || ch == ')' {
+            tokens.push(ch.to_string());
+            chars.next();
+            continue;
+        }
+
+        if ch == '\'' || ch == '"' {
+            let quote = ch;
+            chars.next();
+            let mut literal = String::new();
+            let mut closed = false;
+            for next in chars.by_ref() {
+                if next == quote {
+                    closed = true;
+                    break;
+                }
+                literal.push(next);
+            }
+            if !closed {
+                bail!("unterminated string literal in marker: {}", input);
+            }
+            tokens.push(format!("\"{}\"", literal));
+            continue;
+        }
+
+        if "<>=!".contains(ch) {
+            let mut op = String::new();
+            while let Some(&next) = chars.peek() {
+                if "<>=!".contains(next) {
+                    op.push(next);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(op);
+            continue;
+        }
+
+        let mut word = String::new();
+        while let Some(&next) = chars.peek() {
+            if next.is_whitespace() || "()<>=!'\"".contains(next) {
+                break;
+            }
+            word.push(next);
+            chars.next();
+        }
+        tokens.push(word);
+    }
+
+    Ok(tokens)
+}
+
+fn unquote(token: &str) -> String {
+    token
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .unwrap_or(token)
+        .to_string()
+}
+
+fn peek<'a>(tokens: &'a [String], pos: usize) -> Option<&'a str> {
+    tokens.get(pos).map(String::as_str)
+}
+
+fn parse_or(tokens: &[String], pos: &mut usize) -> Result<MarkerExpr> {
+    let mut expr = parse_and(tokens, pos)?;
+    while peek(tokens, *pos) == Some("or") {
+        *pos += 1;
+        let rhs = parse_and(tokens, pos)?;
+        expr = MarkerExpr::Or(Box::new(expr), Box::new(rhs));
+    }
+    Ok(expr)
+}
+
+fn parse_and(tokens: &[String], pos: &mut usize) -> Result<MarkerExpr> {
+    let mut expr = parse_atom(tokens, pos)?;
+    while peek(tokens, *pos) == Some("and") {
+        *pos += 1;
+        let rhs = parse_atom(tokens, pos)?;
+        expr = MarkerExpr::And(Box::new(expr), Box::new(rhs));
+    }
+    Ok(expr)
+}
+
+fn parse_atom(tokens: &[String], pos: &mut usize) -> Result<MarkerExpr> {
+    if peek(tokens, *pos) == Some("(") {
+        *pos += 1;
+        let expr = parse_or(tokens, pos)?;
+        if peek(tokens, *pos) != Some(")") {
+            bail!("expected closing parenthesis in marker expression");
+        }
+        *pos += 1;
+        return Ok(expr);
+    }
+
+    parse_comparison(tokens, pos)
+}
+
+fn parse_comparison(tokens: &[String], pos: &mut usize) -> Result<MarkerExpr> {
+    let lhs = next_operand(tokens, pos)?;
+    let operator = parse_operator(tokens, pos)?;
+    let rhs = next_operand(tokens, pos)?;
+
+    // Either side may name the marker variable; the other is the literal.
+    match (MarkerVariable::from_token(&lhs), MarkerVariable::from_token(&rhs)) {
+        (Some(variable), None) => Ok(MarkerExpr::Comparison {
+            variable,
+            operator,
+            value: unquote(&rhs),
+        }),
+        // `"3.8" > python_version` means `python_version < "3.8"`, so an
+        // ordered operator is mirrored when the variable is on the right.
+        (None, Some(variable)) => Ok(MarkerExpr::Comparison {
+            variable,
+            operator: match operator {
+                MarkerOperator::Lt => MarkerOperator::Gt,
+                MarkerOperator::LtEq => MarkerOperator::GtEq,
+                MarkerOperator::Gt => MarkerOperator::Lt,
+                MarkerOperator::GtEq => MarkerOperator::LtEq,
+                other => other,
+            },
+            value: unquote(&lhs),
+        }),
+        _ => bail!("unrecognized marker comparison: {} {:?} {}", lhs, operator, rhs),
+    }
+}
+
+/// Parses the operator at `pos`, including the two-word `not in`, which
+/// needs a lookahead to tell the `not` from a bare identifier.
+fn parse_operator(tokens: &[String], pos: &mut usize) -> Result<MarkerOperator> {
+    if peek(tokens, *pos) == Some("not") && peek(tokens, *pos + 1) == Some("in") {
+        *pos += 2;
+        return Ok(MarkerOperator::NotIn);
+    }
+    if peek(tokens, *pos) == Some("in") {
+        *pos += 1;
+        return Ok(MarkerOperator::In);
+    }
+    match peek(tokens, *pos).and_then(MarkerOperator::from_token) {
+        Some(operator) => {
+            *pos += 1;
+            Ok(operator)
+        }
+        None => bail!("expected a comparison operator in marker expression"),
+    }
+}
+
+fn next_operand(tokens: &[String], pos: &mut usize) -> Result<String> {
+    let token = peek(tokens, *pos)
+        .ok_or_else(|| anyhow::anyhow!("unexpected end of marker expression"))?
+        .to_string();
+    *pos += 1;
+    Ok(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_marker, MarkerEnvironment};
+
+    fn linux_env() -> MarkerEnvironment {
+        MarkerEnvironment {
+            python_version: "3.9".to_string(),
+            python_full_version: "3.9.7".to_string(),
+            sys_platform: "linux".to_string(),
+            os_name: "posix".to_string(),
+            platform_machine: "x86_64".to_string(),
+            implementation_name: "cpython".to_string(),
+            extra: None,
+        }
+    }
+
+    #[test]
+    fn check_simple_comparison() {
+        let marker = parse_marker(r#"python_version < "3.8""#).unwrap();
+        assert!(!marker.evaluate(&linux_env()));
+
+        let marker = parse_marker(r#"python_version >= "3.8""#).unwrap();
+        assert!(marker.evaluate(&linux_env()));
+    }
+
+    #[test]
+    fn check_and_or_precedence() {
+        let marker =
+            parse_marker(r#"python_version < "3.8" and sys_platform == "win32""#).unwrap();
+        assert!(!marker.evaluate(&linux_env()));
+
+        let marker =
+            parse_marker(r#"python_version >= "3.8" or sys_platform == "win32""#).unwrap();
+        assert!(marker.evaluate(&linux_env()));
+    }
+
+    #[test]
+    fn check_parentheses() {
+        let marker = parse_marker(
+            r#"(python_version >= "3.8" and sys_platform == "linux") or os_name == "nt""#,
+        )
+        .unwrap();
+        assert!(marker.evaluate(&linux_env()));
+    }
+
+    #[test]
+    fn check_in_and_not_in_operators() {
+        let marker = parse_marker(r#""win" in sys_platform"#).unwrap();
+        assert!(!marker.evaluate(&linux_env()));
+
+        let marker = parse_marker(r#""win" not in sys_platform"#).unwrap();
+        assert!(marker.evaluate(&linux_env()));
+
+        let marker = parse_marker(r#""lin" in sys_platform"#).unwrap();
+        assert!(marker.evaluate(&linux_env()));
+    }
+
+    #[test]
+    fn check_extra_marker_requires_requested_extra() {
+        let marker = parse_marker(r#"extra == "security""#).unwrap();
+        assert!(!marker.evaluate(&linux_env()));
+
+        let mut env = linux_env();
+        env.extra = Some("security".to_string());
+        assert!(marker.evaluate(&env));
+    }
+}
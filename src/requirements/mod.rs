@@ -1,29 +1,39 @@
-mod package_version;
+mod marker;
+mod requirement;
 
+use std::collections::HashSet;
 use std::fmt::Display;
-use std::fs::{read_to_string, File};
-use std::path::PathBuf;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Result};
-use package_version::PackageVersion;
 use pomsky_macro::pomsky;
 use regex::Regex;
 
+pub use crate::package_version::PackageVersion;
+pub use marker::{parse_marker, MarkerEnvironment, MarkerExpr};
+pub use requirement::Pep508Requirement;
+
 static REQUIREMENTS_LINE_PARSER: &str = pomsky!(
     "v"?
     (
-        :op("==" | ">=" | "<=")
+        :op("===" | "==" | "~=" | "!=" | ">=" | "<=" | ">" | "<")
     )
 );
 
+/// The eight comparison operators defined by PEP 440.
 #[derive(Debug, PartialEq, Eq)]
-/// Represents the possible "operators" of a package-version pair.
-///
-/// For now, this is `==`, `>=`, and `<=`
 pub enum PyRequirementsOperator {
     EqualTo,
+    NotEqualTo,
+    LessThan,
     GreaterThan,
-    LesserThan,
+    LessThanOrEqualTo,
+    GreaterThanOrEqualTo,
+    /// Compatible release clause, e.g. `~=2.2`
+    CompatibleRelease,
+    /// Arbitrary (string identity) equality, e.g. `===1.0+local`
+    ArbitraryEqualTo,
 }
 
 impl PyRequirementsOperator {
@@ -36,14 +46,19 @@ impl PyRequirementsOperator {
     /// let c  = PyRequirementsOperator::new("!!").unwrap(); // Also returns an Err
     /// ```
     fn new(op: &str) -> Result<Self, String> {
-        if op.len() > 2 {
+        if op.len() > 3 {
             return Err(format!("Operator is {} long", op.len()));
         }
 
         match op {
             "==" => Ok(Self::EqualTo),
-            ">=" => Ok(Self::GreaterThan),
-            "<=" => Ok(Self::LesserThan),
+            "!=" => Ok(Self::NotEqualTo),
+            "<" => Ok(Self::LessThan),
+            ">" => Ok(Self::GreaterThan),
+            "<=" => Ok(Self::LessThanOrEqualTo),
+            ">=" => Ok(Self::GreaterThanOrEqualTo),
+            "~=" => Ok(Self::CompatibleRelease),
+            "===" => Ok(Self::ArbitraryEqualTo),
             _ => Err(format!("Unknown Operator: {}", op)),
         }
     }
@@ -62,19 +77,218 @@ impl Display for PyRequirementsOperator {
             "{}",
             match self {
                 Self::EqualTo => "==",
-                Self::GreaterThan => ">=",
-                Self::LesserThan => "<=",
+                Self::NotEqualTo => "!=",
+                Self::LessThan => "<",
+                Self::GreaterThan => ">",
+                Self::LessThanOrEqualTo => "<=",
+                Self::GreaterThanOrEqualTo => ">=",
+                Self::CompatibleRelease => "~=",
+                Self::ArbitraryEqualTo => "===",
             }
         )
     }
 }
 
+/// The version half of a [`VersionSpecifier`]: either an exact parsed
+/// version, or an `X.*`/`X.Y.*`/`X.Y.Z.*`/... wildcard prefix of arbitrary
+/// length (only valid alongside `==`/`!=`).
+#[derive(Debug)]
+pub enum VersionMatch {
+    Exact(PackageVersion),
+    WildcardPrefix(Vec<u32>),
+}
+
+impl VersionMatch {
+    fn is_prerelease(&self) -> bool {
+        matches!(self, Self::Exact(version) if version.is_prerelease())
+    }
+}
+
+/// Returns whether `candidate`'s release segments start with `prefix`,
+/// ignoring pre/post/dev/local entirely. Release segments past the end of
+/// `candidate`'s own are treated as zero, matching [`ReleaseHeader`]'s
+/// zero-padded comparison.
+fn matches_wildcard_prefix(candidate: &PackageVersion, prefix: &[u32]) -> bool {
+    prefix
+        .iter()
+        .enumerate()
+        .all(|(idx, segment)| candidate.release.segments.get(idx).copied().unwrap_or(0) == *segment)
+}
+
+/// An operator paired with the version it applies to, e.g. the `>=1.20` half
+/// of `numpy>=1.20`.
+#[derive(Debug)]
+pub struct VersionSpecifier {
+    pub operator: PyRequirementsOperator,
+    pub version: VersionMatch,
+}
+
+impl VersionSpecifier {
+    /// Parses the operator and version/wildcard half of a specifier clause,
+    /// e.g. the `>=1.20` in `numpy>=1.20` or the `==1.1.*` in `foo==1.1.*`.
+    fn parse(operator: PyRequirementsOperator, raw: &str) -> Result<Self> {
+        let version = match raw.strip_suffix(".*") {
+            Some(prefix) => {
+                if !matches!(
+                    operator,
+                    PyRequirementsOperator::EqualTo | PyRequirementsOperator::NotEqualTo
+                ) {
+                    bail!("wildcard versions are only valid with == or !=, found {}", operator);
+                }
+
+                let segments = prefix
+                    .split('.')
+                    .map(str::parse::<u32>)
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                VersionMatch::WildcardPrefix(segments)
+            }
+            None => VersionMatch::Exact(PackageVersion::new(raw)?),
+        };
+
+        Ok(Self { operator, version })
+    }
+
+    /// Tests whether `candidate` is contained by this operator/version pair.
+    ///
+    /// `~=X.Y` is treated as `>=X.Y, ==X.*`, i.e. `candidate` must be no
+    /// older than `X.Y` and must share its leading release segment. The
+    /// exclusive ordered comparisons exclude a boundary-adjacent pre-release
+    /// or dev release (for `<`) or post-release (for `>`) unless the
+    /// boundary itself is one, per PEP 440.
+    pub fn contains(&self, candidate: &PackageVersion) -> bool {
+        match (&self.operator, &self.version) {
+            (PyRequirementsOperator::EqualTo, VersionMatch::WildcardPrefix(prefix)) => {
+                matches_wildcard_prefix(candidate, prefix)
+            }
+            (PyRequirementsOperator::NotEqualTo, VersionMatch::WildcardPrefix(prefix)) => {
+                !matches_wildcard_prefix(candidate, prefix)
+            }
+            (PyRequirementsOperator::EqualTo, VersionMatch::Exact(version)) => candidate == version,
+            (PyRequirementsOperator::NotEqualTo, VersionMatch::Exact(version)) => candidate != version,
+            (PyRequirementsOperator::LessThan, VersionMatch::Exact(version)) => {
+                candidate < version
+                    && !((candidate.pre.is_some() || candidate.dev.is_some())
+                        && !version.is_prerelease()
+                        && candidate.release == version.release)
+            }
+            (PyRequirementsOperator::GreaterThan, VersionMatch::Exact(version)) => {
+                candidate > version
+                    && !(candidate.post.is_some()
+                        && version.post.is_none()
+                        && candidate.release == version.release)
+            }
+            (PyRequirementsOperator::LessThanOrEqualTo, VersionMatch::Exact(version)) => candidate <= version,
+            (PyRequirementsOperator::GreaterThanOrEqualTo, VersionMatch::Exact(version)) => candidate >= version,
+            // `===` bypasses normalization entirely and compares the raw text.
+            (PyRequirementsOperator::ArbitraryEqualTo, VersionMatch::Exact(version)) => {
+                candidate.original == version.original
+            }
+            (PyRequirementsOperator::CompatibleRelease, VersionMatch::Exact(version)) => {
+                let prefix = &version.release.segments[..version.release.segments.len().saturating_sub(1)];
+                candidate >= version && matches_wildcard_prefix(candidate, prefix)
+            }
+            // `VersionSpecifier::parse` rejects every other operator/wildcard pairing.
+            (_, VersionMatch::WildcardPrefix(_)) => {
+                unreachable!("wildcard paired with an operator other than == or !=")
+            }
+        }
+    }
+}
+
+/// A comma-separated list of [`VersionSpecifier`]s, e.g.
+/// `>=1.20,<2.0,!=1.25.0`. A candidate version is contained by the set only
+/// when it is contained by every clause, and — per PEP 440 — pre-release
+/// candidates are excluded unless the set itself names a pre-release or the
+/// caller explicitly allows them.
+#[derive(Debug)]
+pub struct VersionSpecifierSet {
+    pub specifiers: Vec<VersionSpecifier>,
+}
+
+impl VersionSpecifierSet {
+    /// Parses a comma-separated specifier line into its individual clauses.
+    ///
+    /// # Example
+    /// ```
+    /// let set = VersionSpecifierSet::new("numpy>=1.20,<2.0,!=1.25.0");
+    /// ```
+    pub fn new(raw: &str) -> Result<Self> {
+        let regex = Regex::new(REQUIREMENTS_LINE_PARSER).unwrap();
+
+        let specifiers = raw
+            .split(',')
+            .map(|clause| {
+                let clause = clause.trim();
+
+                let res = match regex.captures(clause) {
+                    Some(caps) => caps,
+                    None => bail!("unable to parse specifier clause: {}", clause),
+                };
+
+                let op = res.name("op").unwrap();
+                let (op_start, op_end) = (op.start(), op.end());
+
+                let operator = match PyRequirementsOperator::new(op.as_str()) {
+                    Ok(op) => op,
+                    Err(err) => bail!("Op Parsing returned an error: {}", err),
+                };
+
+                // A clause may still carry its package name (e.g. the first
+                // clause of `numpy>=1.20,<2.0`); only the version past the
+                // operator is relevant here.
+                let _package = &clause[..op_start];
+                VersionSpecifier::parse(operator, &clause[op_end..])
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { specifiers })
+    }
+
+    /// Returns true only when `candidate` is contained by every clause in
+    /// the set, applying PEP 440's default pre-release exclusion.
+    pub fn contains(&self, candidate: &PackageVersion) -> bool {
+        let allow_prereleases = self.specifiers.iter().any(|spec| spec.version.is_prerelease());
+        self.contains_allowing_prereleases(candidate, allow_prereleases)
+    }
+
+    /// Like [`contains`](Self::contains), but the caller decides whether a
+    /// pre-release candidate is considered at all instead of relying on
+    /// whether the set itself names one.
+    pub fn contains_allowing_prereleases(&self, candidate: &PackageVersion, allow_prereleases: bool) -> bool {
+        if (candidate.pre.is_some() || candidate.dev.is_some()) && !allow_prereleases {
+            return false;
+        }
+        self.specifiers.iter().all(|spec| spec.contains(candidate))
+    }
+}
+
 /// Represents a module in a `requirements.txt` file
 #[derive(Debug)]
 pub struct PyRequirementsModule {
     pub package: String,
     pub version: PackageVersion,
     pub operator: PyRequirementsOperator,
+    /// Extras requested via `package[extra1,extra2]==version`.
+    pub extras: Vec<String>,
+    /// The `; marker` suffix, if any (e.g. `python_version < "3.8"`).
+    pub marker: Option<MarkerExpr>,
+}
+
+/// Splits a leading `name[extra1,extra2]` into the bare package name and its
+/// requested extras.
+fn parse_extras(name_part: &str) -> (String, Vec<String>) {
+    let (start, end) = match (name_part.find('['), name_part.find(']')) {
+        (Some(start), Some(end)) if start < end => (start, end),
+        _ => return (name_part.trim().to_string(), Vec::new()),
+    };
+
+    let extras = name_part[start + 1..end]
+        .split(',')
+        .map(|extra| extra.trim().to_string())
+        .filter(|extra| !extra.is_empty())
+        .collect();
+
+    (name_part[..start].trim().to_string(), extras)
 }
 
 impl PyRequirementsModule {
@@ -83,10 +297,21 @@ impl PyRequirementsModule {
     /// # Example
     /// ```
     /// let bs4 = PyRequirementsModule::new("bs4==10.3.2");
+    /// let bs4 = PyRequirementsModule::new("bs4[security]==10.3.2 ; python_version >= \"3.8\"");
     /// ```
     fn new(raw: &str) -> Result<Self> {
+        let (requirement_part, marker_part) = match raw.split_once(';') {
+            Some((requirement, marker)) => (requirement.trim(), Some(marker.trim())),
+            None => (raw.trim(), None),
+        };
+
+        let marker = match marker_part {
+            Some(marker) => Some(marker::parse_marker(marker)?),
+            None => None,
+        };
+
         let regex = Regex::new(REQUIREMENTS_LINE_PARSER).unwrap();
-        let res = match regex.captures(raw) {
+        let res = match regex.captures(requirement_part) {
             Some(caps) => caps,
             None => bail!("unable to parse line"),
         };
@@ -94,6 +319,8 @@ impl PyRequirementsModule {
         let op = res.name("op").unwrap();
         let (op_start, op_end) = (op.start(), op.end());
 
+        let (package, extras) = parse_extras(&requirement_part[..op_start]);
+
         Ok(Self {
             operator: match PyRequirementsOperator::new(
                 res.name("op").unwrap().as_str(),
@@ -101,13 +328,21 @@ impl PyRequirementsModule {
                 Ok(op) => op,
                 Err(err) => bail!("Op Parsing returned an error: {}", err),
             },
-            package: raw[..op_start].to_string(),
-            version: match PackageVersion::new(&raw[op_end..]) {
+            package,
+            version: match PackageVersion::new(&requirement_part[op_end..]) {
                 Ok(ver) => ver,
                 Err(err) => bail!("Package Versioner returned an error: {}", err),
             },
+            extras,
+            marker,
         })
     }
+
+    /// Returns whether this requirement applies in `env`. A requirement with
+    /// no marker always applies.
+    pub fn evaluate(&self, env: &MarkerEnvironment) -> bool {
+        self.marker.as_ref().map_or(true, |marker| marker.evaluate(env))
+    }
 }
 
 impl Display for PyRequirementsModule {
@@ -116,86 +351,315 @@ impl Display for PyRequirementsModule {
     }
 }
 
-/// Represents a `requirements.txt` file
+/// One non-blank, non-comment logical line of a `requirements.txt`/
+/// `constraints.txt` file, classified by the directive it represents.
 #[derive(Debug)]
+enum RequirementsLine {
+    Requirement(PyRequirementsModule),
+    /// `-r`/`--requirement <file>`
+    Include(String),
+    /// `-c`/`--constraint <file>`
+    ConstraintInclude(String),
+    /// `-e`/`--editable <path-or-url>`
+    Editable(String),
+    /// A global option such as `--index-url` or `--extra-index-url`
+    GlobalOption(String, Option<String>),
+}
+
+/// Flags recognized as directives rather than `package==version` lines,
+/// alongside their long forms.
+const INCLUDE_FLAGS: &[&str] = &["-r", "--requirement"];
+const CONSTRAINT_FLAGS: &[&str] = &["-c", "--constraint"];
+const EDITABLE_FLAGS: &[&str] = &["-e", "--editable"];
+const GLOBAL_OPTION_FLAGS: &[&str] = &[
+    "--index-url",
+    "--extra-index-url",
+    "--no-index",
+    "--find-links",
+    "--trusted-host",
+];
+
+/// Matches `line` against one of `flags`, returning the (trimmed) text
+/// following the flag when it matches. The flag must be followed by
+/// whitespace, `=`, or the end of the line so `-requests` doesn't match `-r`.
+fn strip_flag<'a>(line: &'a str, flags: &[&str]) -> Option<&'a str> {
+    for flag in flags {
+        if let Some(rest) = line.strip_prefix(flag) {
+            if rest.is_empty() || rest.starts_with(char::is_whitespace) || rest.starts_with('=') {
+                return Some(rest.trim_start_matches('=').trim());
+            }
+        }
+    }
+    None
+}
+
+/// Joins physical lines that end in a trailing `\` into single logical
+/// lines.
+fn join_continuations(raw: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for physical in raw.split('\n') {
+        let physical = physical.trim_end_matches('\r');
+        match physical.strip_suffix('\\') {
+            Some(stripped) => {
+                current.push_str(stripped);
+                current.push(' ');
+            }
+            None => {
+                current.push_str(physical);
+                lines.push(std::mem::take(&mut current));
+            }
+        }
+    }
+
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    lines
+}
+
+/// Strips a `#` comment, including an inline trailing comment such as
+/// `flask==2.0  # web framework`. A `#` only starts a comment when it is at
+/// the start of the line or preceded by whitespace, so it won't misfire on
+/// URL fragments.
+fn strip_comment(line: &str) -> &str {
+    let mut prev_is_space = true;
+
+    for (idx, ch) in line.char_indices() {
+        if ch == '#' && prev_is_space {
+            return &line[..idx];
+        }
+        prev_is_space = ch.is_whitespace();
+    }
+
+    line
+}
+
+/// Classifies a single logical line of a requirements file.
+fn parse_requirements_line(line: &str) -> Result<RequirementsLine, String> {
+    if let Some(rest) = strip_flag(line, INCLUDE_FLAGS) {
+        return Ok(RequirementsLine::Include(rest.to_string()));
+    }
+    if let Some(rest) = strip_flag(line, CONSTRAINT_FLAGS) {
+        return Ok(RequirementsLine::ConstraintInclude(rest.to_string()));
+    }
+    if let Some(rest) = strip_flag(line, EDITABLE_FLAGS) {
+        return Ok(RequirementsLine::Editable(rest.to_string()));
+    }
+    for &flag in GLOBAL_OPTION_FLAGS {
+        if let Some(rest) = strip_flag(line, &[flag]) {
+            let value = if rest.is_empty() { None } else { Some(rest.to_string()) };
+            return Ok(RequirementsLine::GlobalOption(flag.to_string(), value));
+        }
+    }
+
+    PyRequirementsModule::new(line)
+        .map(RequirementsLine::Requirement)
+        .map_err(|err| err.to_string())
+}
+
+/// Resolves a `-r`/`-c` target relative to the directory of the file
+/// that referenced it.
+fn resolve_relative_path(base_dir: &Path, target: &str) -> PathBuf {
+    let target = PathBuf::from(target);
+    if target.is_absolute() {
+        target
+    } else {
+        base_dir.join(target)
+    }
+}
+
+/// Joins a (possibly relative) include target against the URL of the file
+/// that referenced it, the same way [`resolve_relative_path`] does for local
+/// files.
+fn resolve_relative_url(base_url: &str, target: &str) -> String {
+    if target.starts_with("http://") || target.starts_with("https://") {
+        return target.to_string();
+    }
+
+    match base_url.rfind('/') {
+        Some(idx) => format!("{}/{}", &base_url[..idx], target),
+        None => target.to_string(),
+    }
+}
+
+/// Where a requirements/constraints file's contents come from: a path on
+/// disk, or a URL to download it from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum RequirementsSource {
+    Local(PathBuf),
+    Remote(String),
+}
+
+impl RequirementsSource {
+    /// Interprets `location` as a URL when it has an `http(s)://` scheme,
+    /// and as a local path otherwise.
+    fn parse(location: &str) -> Self {
+        if location.starts_with("http://") || location.starts_with("https://") {
+            Self::Remote(location.to_string())
+        } else {
+            Self::Local(PathBuf::from(location))
+        }
+    }
+
+    /// Resolves an include target (`-r`/`-c`/relative path) found inside
+    /// this source.
+    fn resolve(&self, target: &str) -> Self {
+        if target.starts_with("http://") || target.starts_with("https://") {
+            return Self::Remote(target.to_string());
+        }
+
+        match self {
+            Self::Local(path) => {
+                let base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+                Self::Local(resolve_relative_path(&base_dir, target))
+            }
+            Self::Remote(base_url) => Self::Remote(resolve_relative_url(base_url, target)),
+        }
+    }
+
+    /// Reads the file's contents, either from disk or over HTTP(S).
+    fn read(&self) -> Result<String, String> {
+        match self {
+            Self::Local(path) => {
+                if !path.exists() {
+                    return Err(format!("{:?} does not exist!", path.to_str().unwrap()));
+                }
+                if !path.is_file() {
+                    return Err(format!("{:?} is not a file!", path.to_str().unwrap()));
+                }
+                read_to_string(path).map_err(|err| {
+                    format!("Unable to read file: {:?}: {}", path.to_str().unwrap(), err)
+                })
+            }
+            Self::Remote(url) => reqwest::blocking::get(url)
+                .and_then(|resp| resp.text())
+                .map_err(|err| format!("Unable to download {:?}: {}", url, err)),
+        }
+    }
+}
+
+/// Represents a `requirements.txt` file, together with everything it
+/// transitively pulls in via `-r`/`-c` includes.
+#[derive(Debug, Default)]
 pub struct PyRequirements {
-    file: PathBuf,
-    /// ALl the dependencies of a project
+    /// Every dependency of the project, gathered from this file and any
+    /// file it includes via `-r`/`--requirement`.
     pub requirements: Vec<PyRequirementsModule>,
+    /// Version constraints gathered via `-c`/`--constraint`. These pin
+    /// allowed versions without, by themselves, requesting installation.
+    pub constraints: Vec<PyRequirementsModule>,
+    /// Raw `-e`/`--editable` targets (local paths or VCS URLs).
+    pub editables: Vec<String>,
+    /// Global options such as `--index-url`, in the order they were seen.
+    pub global_options: Vec<(String, Option<String>)>,
 }
 
 impl PyRequirements {
-    /// Represents a `requirements.txt` file
+    /// Represents a `requirements.txt` file. `location` may be a local path
+    /// or an `http(s)://` URL.
     ///
     /// # Example
     /// ```
-    /// let req = PyRequirements::new(PathBuf::from("project/requirements.txt"));
+    /// let req = PyRequirements::new("project/requirements.txt");
+    /// let req = PyRequirements::new("https://example.com/requirements.txt");
     /// ```
-    pub fn new(path: &PathBuf) -> Result<Self, String> {
-        if !path.exists() {
-            return Err(format!("{:?} does not exist!", path.to_str().unwrap()));
-        }
-
-        // Check if the path specified is a file
-        if !path.is_file() {
-            return Err(format!("{:?} is not a file!", path.to_str().unwrap()));
-        }
+    pub fn new(location: &str) -> Result<Self, String> {
+        let mut result = Self::default();
+        let mut visited = HashSet::new();
+        result.load_source(&RequirementsSource::parse(location), &mut visited, false)?;
+        Ok(result)
+    }
 
-        // Then check if that file is a "requirements.txt" file
-        // TODO: Use some magic to see if the file can be parsed
-        //       and then use that to check instead of this
-        if !path.ends_with("requirements.txt") {
-            return Err(format!(
-                "File specified is not a 'requirements.txt' file: {:?}",
-                path.to_str().unwrap()
-            ));
+    /// Loads `source`, recursing into any `-r`/`-c` includes it references.
+    /// `visited` tracks every source already loaded by this call tree, so a
+    /// file (or URL) included more than once is read only the first time,
+    /// and one that (transitively) includes itself doesn't loop forever.
+    fn load_source(
+        &mut self,
+        source: &RequirementsSource,
+        visited: &mut HashSet<RequirementsSource>,
+        as_constraint: bool,
+    ) -> Result<(), String> {
+        let canonical = match source {
+            RequirementsSource::Local(path) => {
+                RequirementsSource::Local(path.canonicalize().unwrap_or_else(|_| path.clone()))
+            }
+            remote => remote.clone(),
+        };
+        if !visited.insert(canonical) {
+            // Already loaded, further up the include chain or by an earlier
+            // include.
+            return Ok(());
         }
 
-        let binding = read_to_string(&path).expect(
-            format!("Unable to read file: {:?}", path.to_str().unwrap()).as_str(),
-        );
+        let contents = source.read()?;
 
-        let raw: Vec<&str> = binding.split("\n").collect();
-        let mut requirements = Vec::<PyRequirementsModule>::new();
+        for (lineno, line) in join_continuations(&contents).iter().enumerate() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
 
-        for (lineno, line) in raw.iter().enumerate() {
-            match PyRequirementsModule::new(line) {
-                Ok(py_mod) => requirements.push(py_mod),
-                Err(err) => {
-                    println!("Unable to parse line {}: {}", lineno, err)
+            match parse_requirements_line(line) {
+                Ok(RequirementsLine::Include(target)) => {
+                    self.load_source(&source.resolve(&target), visited, as_constraint)?;
                 }
+                Ok(RequirementsLine::ConstraintInclude(target)) => {
+                    self.load_source(&source.resolve(&target), visited, true)?;
+                }
+                Ok(RequirementsLine::Editable(target)) => self.editables.push(target),
+                Ok(RequirementsLine::GlobalOption(name, value)) => {
+                    self.global_options.push((name, value))
+                }
+                Ok(RequirementsLine::Requirement(module)) => {
+                    if as_constraint {
+                        self.constraints.push(module);
+                    } else {
+                        self.requirements.push(module);
+                    }
+                }
+                Err(err) => println!("Unable to parse line {}: {}", lineno, err),
             }
         }
 
-        // I FORGOR TO HAVE IT RETURN ITSELF
-        // :((((((((((((((((((((((((((((((
-        Ok(Self {
-            file: path.to_path_buf(),
-            requirements: requirements,
-        })
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use anyhow::{bail, Result};
-    use std::path::PathBuf;
+    use anyhow::Result;
 
     use super::PyRequirements;
     use super::PyRequirementsModule;
     use super::PyRequirementsOperator;
+    use super::VersionSpecifierSet;
+    use super::{PackageVersion, VersionSpecifier};
 
     #[test]
     fn check_py_requirements_operator() -> Result<()> {
         let eq = PyRequirementsOperator::new("==").unwrap();
-        let gt = PyRequirementsOperator::new(">=").unwrap();
-        let lt = PyRequirementsOperator::new("<=").unwrap();
+        let ne = PyRequirementsOperator::new("!=").unwrap();
+        let lt = PyRequirementsOperator::new("<").unwrap();
+        let gt = PyRequirementsOperator::new(">").unwrap();
+        let le = PyRequirementsOperator::new("<=").unwrap();
+        let ge = PyRequirementsOperator::new(">=").unwrap();
+        let compat = PyRequirementsOperator::new("~=").unwrap();
+        let arbitrary = PyRequirementsOperator::new("===").unwrap();
 
         let e1 = PyRequirementsOperator::new("AMOGUSSSSSSSSSSSSS");
 
         assert_eq!(eq, PyRequirementsOperator::EqualTo);
+        assert_eq!(ne, PyRequirementsOperator::NotEqualTo);
+        assert_eq!(lt, PyRequirementsOperator::LessThan);
         assert_eq!(gt, PyRequirementsOperator::GreaterThan);
-        assert_eq!(lt, PyRequirementsOperator::LesserThan);
+        assert_eq!(le, PyRequirementsOperator::LessThanOrEqualTo);
+        assert_eq!(ge, PyRequirementsOperator::GreaterThanOrEqualTo);
+        assert_eq!(compat, PyRequirementsOperator::CompatibleRelease);
+        assert_eq!(arbitrary, PyRequirementsOperator::ArbitraryEqualTo);
 
         assert!(e1.is_err(), "e1 is supposed to be an Error!");
 
@@ -223,8 +687,8 @@ mod tests {
 
     #[test]
     fn check_py_requirements_file_parser() -> Result<()> {
-        let path = PathBuf::from("test/requirements.txt");
-        let raw = PyRequirements::new(&path);
+        let path = "test/requirements.txt";
+        let raw = PyRequirements::new(path);
 
         assert!(
             raw.is_ok(),
@@ -234,4 +698,208 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn check_comments_are_stripped_and_continuations_joined() -> Result<()> {
+        let stripped = super::strip_comment("flask==2.0  # web framework");
+        assert_eq!(stripped.trim(), "flask==2.0");
+
+        let joined = super::join_continuations("numpy\\\n==1.26.4");
+        assert_eq!(joined, vec!["numpy ==1.26.4".to_string()]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_requirements_source_parsing_and_resolution() -> Result<()> {
+        use super::RequirementsSource;
+
+        assert_eq!(
+            RequirementsSource::parse("https://example.com/requirements.txt"),
+            RequirementsSource::Remote("https://example.com/requirements.txt".to_string())
+        );
+        assert_eq!(
+            RequirementsSource::parse("requirements.txt"),
+            RequirementsSource::Local("requirements.txt".into())
+        );
+
+        // A relative -r target resolves against the directory/URL of the
+        // file that referenced it.
+        let remote = RequirementsSource::Remote("https://example.com/deps/requirements.txt".to_string());
+        assert_eq!(
+            remote.resolve("dev-requirements.txt"),
+            RequirementsSource::Remote("https://example.com/deps/dev-requirements.txt".to_string())
+        );
+
+        let local = RequirementsSource::Local("project/requirements.txt".into());
+        assert_eq!(
+            local.resolve("dev-requirements.txt"),
+            RequirementsSource::Local("project/dev-requirements.txt".into())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_py_requirements_nested_includes() -> Result<()> {
+        let path = "test/grammar/requirements.txt";
+        let parsed = PyRequirements::new(path);
+
+        assert!(
+            parsed.is_ok(),
+            "Unable to parse file {:?}: {:?}",
+            path,
+            parsed.unwrap_err()
+        );
+
+        let parsed = parsed.unwrap();
+        // flask and requests come from the top-level file, numpy from its
+        // `-r` include.
+        assert_eq!(parsed.requirements.len(), 3);
+        assert!(parsed
+            .requirements
+            .iter()
+            .any(|module| module.package == "flask" && module.version.to_string() == "2.0"));
+        assert!(parsed
+            .requirements
+            .iter()
+            .any(|module| module.package.trim() == "numpy"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_version_specifier_contains() -> Result<()> {
+        use super::VersionMatch;
+
+        let spec = VersionSpecifier {
+            operator: PyRequirementsOperator::GreaterThanOrEqualTo,
+            version: VersionMatch::Exact(PackageVersion::new("1.20")?),
+        };
+        assert!(spec.contains(&PackageVersion::new("1.20")?));
+        assert!(spec.contains(&PackageVersion::new("1.21")?));
+        assert!(!spec.contains(&PackageVersion::new("1.19")?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_compatible_release_contains() -> Result<()> {
+        use super::VersionMatch;
+
+        // ~=2.2 means >=2.2,<3
+        let spec = VersionSpecifier {
+            operator: PyRequirementsOperator::CompatibleRelease,
+            version: VersionMatch::Exact(PackageVersion::new("2.2")?),
+        };
+        assert!(spec.contains(&PackageVersion::new("2.2")?));
+        assert!(spec.contains(&PackageVersion::new("2.9")?));
+        assert!(!spec.contains(&PackageVersion::new("3.0")?));
+        assert!(!spec.contains(&PackageVersion::new("2.1")?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_wildcard_specifier() -> Result<()> {
+        let set = VersionSpecifierSet::new("==1.1.*")?;
+        assert!(set.contains(&PackageVersion::new("1.1.0")?));
+        assert!(set.contains(&PackageVersion::new("1.1.9")?));
+        assert!(!set.contains(&PackageVersion::new("1.2.0")?));
+
+        let set = VersionSpecifierSet::new("!=1.1.*")?;
+        assert!(!set.contains(&PackageVersion::new("1.1.0")?));
+        assert!(set.contains(&PackageVersion::new("1.2.0")?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_wildcard_specifier_with_more_than_two_segments() -> Result<()> {
+        let set = VersionSpecifierSet::new("==1.2.3.*")?;
+        assert!(set.contains(&PackageVersion::new("1.2.3.4")?));
+        assert!(!set.contains(&PackageVersion::new("1.2.4.0")?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_arbitrary_equal_to_contains() -> Result<()> {
+        let set = VersionSpecifierSet::new("===1.0+local")?;
+        assert!(set.contains(&PackageVersion::new("1.0+local")?));
+        // `===` compares the raw text, so normalization differences don't match.
+        assert!(!set.contains(&PackageVersion::new("v1.0+local")?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_exclusive_comparisons_exclude_boundary_prerelease() -> Result<()> {
+        let set = VersionSpecifierSet::new("<2.0")?;
+        // 2.0.dev1 < 2.0 numerically, but PEP 440 excludes it as a
+        // pre-release of the exclusive boundary unless 2.0 is itself one.
+        assert!(!set.contains_allowing_prereleases(&PackageVersion::new("2.0.dev1")?, true));
+        assert!(set.contains_allowing_prereleases(&PackageVersion::new("1.9")?, true));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_prereleases_excluded_by_default() -> Result<()> {
+        let set = VersionSpecifierSet::new(">=1.0")?;
+        assert!(!set.contains(&PackageVersion::new("1.1a1")?));
+        assert!(set.contains_allowing_prereleases(&PackageVersion::new("1.1a1")?, true));
+
+        // Dev releases are excluded by default alongside pre-releases.
+        assert!(!set.contains(&PackageVersion::new("1.1.dev1")?));
+        assert!(set.contains_allowing_prereleases(&PackageVersion::new("1.1.dev1")?, true));
+
+        // A set that itself names a pre-release allows pre-release matches.
+        let set = VersionSpecifierSet::new(">=1.1a1")?;
+        assert!(set.contains(&PackageVersion::new("1.1a1")?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_py_requirements_module_extras_and_marker() -> Result<()> {
+        use super::MarkerEnvironment;
+
+        let module =
+            PyRequirementsModule::new("requests[security,socks]>=2.0 ; python_version >= \"3.8\"")?;
+
+        assert_eq!(module.package, "requests");
+        assert_eq!(module.extras, vec!["security".to_string(), "socks".to_string()]);
+        assert!(module.marker.is_some());
+
+        let old_python = MarkerEnvironment {
+            python_version: "3.7".to_string(),
+            ..Default::default()
+        };
+        let new_python = MarkerEnvironment {
+            python_version: "3.9".to_string(),
+            ..Default::default()
+        };
+        assert!(!module.evaluate(&old_python));
+        assert!(module.evaluate(&new_python));
+
+        // A requirement without a marker always applies.
+        let unconditional = PyRequirementsModule::new("numpy==1.26.4")?;
+        assert!(unconditional.extras.is_empty());
+        assert!(unconditional.evaluate(&old_python));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_version_specifier_set() -> Result<()> {
+        let set = VersionSpecifierSet::new(">=1.20,<2.0,!=1.25.0")?;
+
+        assert!(set.contains(&PackageVersion::new("1.20")?));
+        assert!(!set.contains(&PackageVersion::new("1.25.0")?));
+        assert!(!set.contains(&PackageVersion::new("2.0")?));
+        assert!(!set.contains(&PackageVersion::new("1.19")?));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,209 @@
+//! Parsing of standalone PEP 508 requirement strings, e.g.
+//! `requests[security]>=2.0,<3.0 ; python_version >= "3.8"`.
+//!
+//! Unlike [`PyRequirementsModule`](super::PyRequirementsModule), which is
+//! scoped to a single `requirements.txt` line and its single operator/version
+//! pair, a [`Pep508Requirement`] carries a full comma-separated specifier
+//! set and may be built from any PEP 508 requirement string, not just a line
+//! read from a file.
+
+use anyhow::{bail, Result};
+use regex::Regex;
+
+use super::marker::{self, MarkerEnvironment, MarkerExpr};
+use super::{parse_extras, VersionSpecifierSet, REQUIREMENTS_LINE_PARSER};
+
+/// A parsed PEP 508 requirement: a package name, its requested extras, the
+/// version specifier set constraining it, an optional direct-reference URL,
+/// and an optional environment marker.
+///
+/// A requirement constrains a package either by version specifiers
+/// (`requests>=2.0`) or, mutually exclusively, by a direct `@ URL` reference
+/// (`requests @ https://example.com/requests.whl`); `specifiers` is empty
+/// whenever `url` is set.
+#[derive(Debug)]
+pub struct Pep508Requirement {
+    pub name: String,
+    pub extras: Vec<String>,
+    pub specifiers: VersionSpecifierSet,
+    pub url: Option<String>,
+    pub marker: Option<MarkerExpr>,
+}
+
+impl Pep508Requirement {
+    /// Parses a PEP 508 requirement string.
+    ///
+    /// # Example
+    /// ```
+    /// let req = Pep508Requirement::parse(r#"requests[security]>=2.0,<3.0 ; python_version >= "3.8""#);
+    /// let req = Pep508Requirement::parse("requests @ https://example.com/requests.whl");
+    /// ```
+    pub fn parse(raw: &str) -> Result<Self> {
+        let (requirement_part, marker_part) = match raw.split_once(';') {
+            Some((requirement, marker)) => (requirement.trim(), Some(marker.trim())),
+            None => (raw.trim(), None),
+        };
+
+        let marker = marker_part.map(marker::parse_marker).transpose()?;
+
+        let (name_part, specifiers, url) = match requirement_part.split_once('@') {
+            Some((name_part, url_part)) => {
+                let url_part = url_part.trim();
+                if url_part.is_empty() {
+                    bail!("requirement names an empty @ URL: {}", raw);
+                }
+                (
+                    name_part.trim(),
+                    VersionSpecifierSet { specifiers: Vec::new() },
+                    Some(url_part.to_string()),
+                )
+            }
+            // A parenthesized specifier set, e.g. `numpy (>=1.20,<2.0)`, wraps
+            // the whole specifier region, not just the part from the first
+            // operator on — strip it off the requirement before hunting for
+            // an operator, or the `(` is left stuck on the name and the `)`
+            // on the last clause.
+            None if requirement_part.trim_end().ends_with(')') => {
+                let trimmed = requirement_part.trim_end();
+                let open = trimmed
+                    .rfind('(')
+                    .ok_or_else(|| anyhow::anyhow!("unmatched ) in requirement: {}", raw))?;
+                let name_part = trimmed[..open].trim();
+                let spec_part = trimmed[open + 1..trimmed.len() - 1].trim();
+                (name_part, VersionSpecifierSet::new(spec_part)?, None)
+            }
+            None => {
+                let regex = Regex::new(REQUIREMENTS_LINE_PARSER).unwrap();
+                match regex.find(requirement_part) {
+                    Some(op_match) => (
+                        &requirement_part[..op_match.start()],
+                        VersionSpecifierSet::new(requirement_part[op_match.start()..].trim())?,
+                        None,
+                    ),
+                    // A bare requirement, e.g. `requests`, is unconstrained.
+                    None => (requirement_part, VersionSpecifierSet { specifiers: Vec::new() }, None),
+                }
+            }
+        };
+
+        let (name, extras) = parse_extras(name_part);
+        if name.is_empty() {
+            bail!("requirement is missing a package name: {}", raw);
+        }
+
+        Ok(Self {
+            name,
+            extras,
+            specifiers,
+            url,
+            marker,
+        })
+    }
+
+    /// Returns whether this requirement applies in `env`. A requirement with
+    /// no marker always applies.
+    pub fn evaluate(&self, env: &MarkerEnvironment) -> bool {
+        self.marker.as_ref().map_or(true, |marker| marker.evaluate(env))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pep508Requirement;
+    use crate::requirements::marker::MarkerEnvironment;
+
+    fn linux_env() -> MarkerEnvironment {
+        MarkerEnvironment {
+            python_version: "3.9".to_string(),
+            python_full_version: "3.9.7".to_string(),
+            sys_platform: "linux".to_string(),
+            os_name: "posix".to_string(),
+            platform_machine: "x86_64".to_string(),
+            implementation_name: "cpython".to_string(),
+            extra: None,
+        }
+    }
+
+    #[test]
+    fn check_parses_name_extras_specifier_and_marker() -> Result<(), anyhow::Error> {
+        let req = Pep508Requirement::parse(
+            r#"requests[security,socks]>=2.0,<3.0 ; python_version >= "3.8""#,
+        )?;
+
+        assert_eq!(req.name, "requests");
+        assert_eq!(req.extras, vec!["security".to_string(), "socks".to_string()]);
+        assert!(req.evaluate(&linux_env()));
+
+        let old_python = MarkerEnvironment {
+            python_version: "3.6".to_string(),
+            ..linux_env()
+        };
+        assert!(!req.evaluate(&old_python));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_parses_bare_requirement_with_no_specifier_or_marker() -> Result<(), anyhow::Error> {
+        let req = Pep508Requirement::parse("requests")?;
+
+        assert_eq!(req.name, "requests");
+        assert!(req.extras.is_empty());
+        assert!(req.specifiers.specifiers.is_empty());
+        assert!(req.marker.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_parses_parenthesized_specifier() -> Result<(), anyhow::Error> {
+        use super::super::PackageVersion;
+
+        let req = Pep508Requirement::parse("numpy (>=1.20,<2.0)")?;
+
+        assert_eq!(req.name, "numpy");
+        assert!(req.specifiers.contains(&PackageVersion::new("1.21")?));
+        assert!(!req.specifiers.contains(&PackageVersion::new("2.0")?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_missing_package_name_is_rejected() {
+        assert!(Pep508Requirement::parse(">=1.0").is_err());
+    }
+
+    #[test]
+    fn check_parses_direct_url_reference() -> Result<(), anyhow::Error> {
+        let req =
+            Pep508Requirement::parse("requests @ https://example.com/requests-2.0-py3-none-any.whl")?;
+
+        assert_eq!(req.name, "requests");
+        assert_eq!(
+            req.url,
+            Some("https://example.com/requests-2.0-py3-none-any.whl".to_string())
+        );
+        assert!(req.specifiers.specifiers.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_parses_direct_url_reference_with_extras_and_marker() -> Result<(), anyhow::Error> {
+        let req = Pep508Requirement::parse(
+            r#"requests[security] @ https://example.com/requests.whl ; python_version >= "3.8""#,
+        )?;
+
+        assert_eq!(req.name, "requests");
+        assert_eq!(req.extras, vec!["security".to_string()]);
+        assert_eq!(req.url, Some("https://example.com/requests.whl".to_string()));
+        assert!(req.evaluate(&linux_env()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_empty_url_is_rejected() {
+        assert!(Pep508Requirement::parse("requests @ ").is_err());
+    }
+}
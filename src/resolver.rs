@@ -0,0 +1,271 @@
+//! A dependency resolver that, given a project's top-level requirements,
+//! pulls release metadata from PyPI and finds one version of each
+//! (transitively) required package that satisfies every constraint placed
+//! on it.
+//!
+//! This is a plain backtracking search, not a full PubGrub implementation:
+//! there is no incompatibility set, no unit propagation, and no
+//! conflict-driven clause learning or backjumping. Candidates are tried
+//! newest-first, and when a choice leaves some other package with no
+//! release left that satisfies every constraint placed on it, the resolver
+//! discards that choice wholesale and tries the next-newest candidate for
+//! the package that made it, rather than learning which packages were
+//! actually involved in the conflict.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+
+use crate::pypi::{request_package_info, request_package_version_info};
+use crate::requirements::{
+    parse_marker, MarkerEnvironment, PackageVersion, PyRequirementsModule, VersionSpecifierSet,
+};
+
+/// One package pinned to a single version, as selected by [`resolve`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinnedPackage {
+    pub name: String,
+    pub version: String,
+    /// `sha256` digests of every distribution PyPI lists for this release,
+    /// taken from `PyPIData::urls`, for lockfile hash-pinning.
+    pub hashes: Vec<String>,
+}
+
+/// Resolves `root_requirements` against packages hosted on `package_index`
+/// for the given marker environment, returning one pinned release per
+/// (transitively) required package.
+///
+/// # Example
+/// ```
+/// let lockfile = resolve(&project.requirements, "https://pypi.org/", &env);
+/// ```
+pub fn resolve(
+    root_requirements: &[PyRequirementsModule],
+    package_index: &str,
+    env: &MarkerEnvironment,
+) -> Result<Vec<PinnedPackage>> {
+    let mut decisions: HashMap<String, (PackageVersion, Vec<String>)> = HashMap::new();
+
+    for module in root_requirements {
+        let specifiers =
+            VersionSpecifierSet::new(&format!("{}{}", module.operator, module.version))?;
+        resolve_package(&module.package, &specifiers, package_index, env, &mut decisions)?;
+    }
+
+    let mut pinned: Vec<PinnedPackage> = decisions
+        .into_iter()
+        .map(|(name, (version, hashes))| PinnedPackage {
+            name,
+            version: version.to_string(),
+            hashes,
+        })
+        .collect();
+    pinned.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(pinned)
+}
+
+/// Resolves a single package against `specifiers`, recursing into its own
+/// dependencies. Candidate releases are tried newest-first and backtracked
+/// out of when a deeper choice turns out to be unsatisfiable.
+fn resolve_package(
+    name: &str,
+    specifiers: &VersionSpecifierSet,
+    package_index: &str,
+    env: &MarkerEnvironment,
+    decisions: &mut HashMap<String, (PackageVersion, Vec<String>)>,
+) -> Result<()> {
+    if let Some((existing, _)) = decisions.get(name) {
+        if specifiers.contains(existing) {
+            return Ok(());
+        }
+        bail!(
+            "{} is already pinned to {}, which does not satisfy a later constraint",
+            name,
+            existing
+        );
+    }
+
+    let info = request_package_info(name, package_index)
+        .map_err(|err| anyhow::anyhow!("failed to fetch {} from {}: {}", name, package_index, err))?;
+
+    if !info.info.requires_python.trim().is_empty() {
+        let required_python = VersionSpecifierSet::new(&info.info.requires_python)?;
+        let running_python = PackageVersion::new(&env.python_version)?;
+        if !required_python.contains(&running_python) {
+            bail!(
+                "{} requires Python {}, which does not match the running {}",
+                name,
+                info.info.requires_python,
+                env.python_version
+            );
+        }
+    }
+
+    let mut candidates: Vec<PackageVersion> = info
+        .releases
+        .as_object()
+        .into_iter()
+        .flat_map(|releases| releases.keys())
+        .filter_map(|raw| PackageVersion::new(raw).ok())
+        .filter(|version| specifiers.contains(version))
+        .collect();
+    candidates.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+
+    if candidates.is_empty() {
+        bail!("no release of {} matches the requested constraints", name);
+    }
+
+    for candidate in candidates {
+        // A fetch failure for this candidate is treated the same as an
+        // unsatisfiable candidate: move on to the next-newest one rather
+        // than aborting the whole resolution.
+        let version_info =
+            match request_package_version_info(name, &candidate.to_string(), package_index) {
+                Ok(version_info) => version_info,
+                Err(_) => continue,
+            };
+        let dependencies = requires_dist(&version_info.info.requires_dist, env);
+        let hashes = extract_hashes(&version_info.urls);
+
+        // Snapshot the whole decision set, not just this package's own pin,
+        // so that if this candidate fails deeper in the recursion, every
+        // nested pin it caused is rolled back along with it rather than
+        // leaking into the next candidate's attempt.
+        let snapshot = decisions.clone();
+        decisions.insert(name.to_string(), (candidate, hashes));
+
+        let outcome = dependencies.iter().try_for_each(|(dep_name, dep_specifiers)| {
+            let dep_specifiers = VersionSpecifierSet::new(dep_specifiers)?;
+            resolve_package(dep_name, &dep_specifiers, package_index, env, decisions)
+        });
+
+        match outcome {
+            Ok(()) => return Ok(()),
+            Err(_) => {
+                *decisions = snapshot;
+            }
+        }
+    }
+
+    bail!(
+        "no release of {} has dependencies satisfiable alongside the rest of the resolution",
+        name
+    );
+}
+
+/// Collects the `sha256` digest of every distribution PyPI lists for a
+/// release, from its `PyPIData::urls` entries.
+fn extract_hashes(urls: &[serde_json::value::Value]) -> Vec<String> {
+    urls.iter()
+        .filter_map(|url| url.get("digests")?.get("sha256")?.as_str())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Pulls `(name, specifiers)` pairs out of a package's `requires_dist`
+/// metadata. Entries carrying an environment marker (e.g.
+/// `; extra == "security"`) are kept only if that marker evaluates to true
+/// against `env`, rather than being dropped outright.
+fn requires_dist(raw: &serde_json::value::Value, env: &MarkerEnvironment) -> Vec<(String, String)> {
+    raw.as_array()
+        .map(|entries| entries.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|entry| entry.as_str())
+        .filter(|entry| requires_dist_entry_applies(entry, env))
+        .filter_map(|entry| parse_requires_dist_entry(entry.split(';').next().unwrap_or(entry)))
+        .collect()
+}
+
+/// Returns whether a `requires_dist` entry's trailing `; marker` (if any)
+/// evaluates to true against `env`. Entries with no marker always apply.
+fn requires_dist_entry_applies(entry: &str, env: &MarkerEnvironment) -> bool {
+    match entry.split_once(';') {
+        Some((_, marker)) => parse_marker(marker.trim())
+            .map(|marker| marker.evaluate(env))
+            .unwrap_or(false),
+        None => true,
+    }
+}
+
+/// Parses a single legacy `requires_dist` entry, e.g. `requests (>=2.0,<3.0)`
+/// or `certifi (>=2017.4.17)`. Only the `name (specifiers)` shape is
+/// understood; entries without a parenthesized specifier are skipped.
+fn parse_requires_dist_entry(raw: &str) -> Option<(String, String)> {
+    let open = raw.find('(')?;
+    let close = raw.find(')')?;
+    let name = raw[..open].trim().to_string();
+    let specifiers = raw[open + 1..close].trim().to_string();
+
+    if name.is_empty() || specifiers.is_empty() {
+        return None;
+    }
+
+    Some((name, specifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract_hashes, parse_requires_dist_entry, requires_dist_entry_applies};
+    use crate::requirements::MarkerEnvironment;
+
+    fn linux_env() -> MarkerEnvironment {
+        MarkerEnvironment {
+            python_version: "3.9".to_string(),
+            python_full_version: "3.9.7".to_string(),
+            sys_platform: "linux".to_string(),
+            os_name: "posix".to_string(),
+            platform_machine: "x86_64".to_string(),
+            implementation_name: "cpython".to_string(),
+            extra: None,
+        }
+    }
+
+    #[test]
+    fn check_parses_legacy_requires_dist_entry() {
+        assert_eq!(
+            parse_requires_dist_entry("requests (>=2.0,<3.0)"),
+            Some(("requests".to_string(), ">=2.0,<3.0".to_string()))
+        );
+        assert_eq!(
+            parse_requires_dist_entry("certifi (>=2017.4.17)"),
+            Some(("certifi".to_string(), ">=2017.4.17".to_string()))
+        );
+    }
+
+    #[test]
+    fn check_skips_entries_without_a_specifier() {
+        assert_eq!(parse_requires_dist_entry("requests"), None);
+    }
+
+    #[test]
+    fn check_unmarked_entry_always_applies() {
+        assert!(requires_dist_entry_applies("requests (>=2.0,<3.0)", &linux_env()));
+    }
+
+    #[test]
+    fn check_marker_is_evaluated_not_blanket_dropped() {
+        let env = linux_env();
+        assert!(requires_dist_entry_applies(
+            r#"pywin32 (>=300) ; sys_platform == "win32""#,
+            &env
+        ) == false);
+        assert!(requires_dist_entry_applies(
+            r#"fcntl-backport (>=1.0) ; sys_platform == "linux""#,
+            &env
+        ));
+    }
+
+    #[test]
+    fn check_extracts_sha256_digests_from_urls() {
+        let urls = serde_json::json!([
+            {"digests": {"sha256": "abc123", "md5": "ignored"}},
+            {"digests": {"sha256": "def456"}},
+        ]);
+        assert_eq!(
+            extract_hashes(urls.as_array().unwrap()),
+            vec!["abc123".to_string(), "def456".to_string()]
+        );
+    }
+}
@@ -0,0 +1,327 @@
+//! A version value that may or may not be PEP 440 compliant, for
+//! interoperating with dependencies whose upstream project doesn't follow
+//! PEP 440 (e.g. a git-described tag, or a scheme like semver that happens
+//! to overlap with PEP 440 syntax for simple cases but diverges on pre/post
+//! release semantics).
+//!
+//! There is no universal rule for comparing across schemes, so a
+//! [`Version`] only supports ordering against another of the same scheme;
+//! comparing a [`Version::Pep440`] against a [`Version::Semver`] or
+//! [`Version::Other`] is considered incomparable rather than guessed at.
+//! [`Version::parse_like`] exists for the common case where a caller already
+//! knows which scheme a package uses and wants every candidate release
+//! parsed under that same scheme, rather than re-detecting it release by
+//! release.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::package_version::PackageVersion;
+
+/// A [semver.org](https://semver.org) version: `MAJOR.MINOR.PATCH[-PRE][+BUILD]`.
+#[derive(Debug, Clone)]
+pub struct SemverVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// The dot-separated identifiers after a `-`, e.g. `["alpha", "1"]` for
+    /// `-alpha.1`. Compared per semver's precedence rules: a version with no
+    /// pre-release identifiers outranks one that has them, and otherwise
+    /// identifiers are compared pairwise, with numeric identifiers compared
+    /// numerically and always ranking below alphanumeric ones.
+    pub pre: Vec<String>,
+    /// The build metadata after a `+`, e.g. `"build.5"` for `+build.5`.
+    /// Carried for round-tripping but, per the semver spec, ignored when
+    /// determining precedence.
+    pub build: Option<String>,
+}
+
+impl SemverVersion {
+    /// Parses `raw` as a strict `MAJOR.MINOR.PATCH[-PRE][+BUILD]` semver
+    /// string, returning `None` if it isn't one.
+    pub fn parse(raw: &str) -> Option<Self> {
+        lazy_static! {
+            static ref SEMVER_VALIDATOR: Regex =
+                Regex::new(r"^(\d+)\.(\d+)\.(\d+)(?:-([0-9A-Za-z.-]+))?(?:\+([0-9A-Za-z.-]+))?$")
+                    .unwrap();
+        }
+
+        let captures = SEMVER_VALIDATOR.captures(raw.trim())?;
+        let major = captures.get(1)?.as_str().parse().ok()?;
+        let minor = captures.get(2)?.as_str().parse().ok()?;
+        let patch = captures.get(3)?.as_str().parse().ok()?;
+        let pre = captures
+            .get(4)
+            .map(|m| m.as_str().split('.').map(str::to_string).collect())
+            .unwrap_or_default();
+        let build = captures.get(5).map(|m| m.as_str().to_string());
+
+        Some(Self {
+            major,
+            minor,
+            patch,
+            pre,
+            build,
+        })
+    }
+}
+
+// `build` is carried for round-tripping but ignored for precedence, so
+// `PartialEq`/`Eq` are implemented by hand over `(major, minor, patch, pre)`
+// rather than derived, keeping them consistent with `Ord` (which also
+// ignores `build`).
+impl PartialEq for SemverVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SemverVersion {}
+
+impl PartialOrd for SemverVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SemverVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| compare_pre_release(&self.pre, &other.pre))
+    }
+}
+
+/// Compares two pre-release identifier lists per semver's precedence rules.
+/// A version with no pre-release identifiers always outranks one that has
+/// them; otherwise identifiers are compared pairwise (numeric identifiers
+/// compared numerically and always ranking below alphanumeric ones), and a
+/// list that's a prefix of the other is lower precedence.
+fn compare_pre_release(a: &[String], b: &[String]) -> Ordering {
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => return Ordering::Equal,
+        (true, false) => return Ordering::Greater,
+        (false, true) => return Ordering::Less,
+        (false, false) => {}
+    }
+
+    for (x, y) in a.iter().zip(b.iter()) {
+        let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => x.cmp(y),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    a.len().cmp(&b.len())
+}
+
+impl fmt::Display for SemverVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre.join("."))?;
+        }
+        if let Some(build) = &self.build {
+            write!(f, "+{}", build)?;
+        }
+        Ok(())
+    }
+}
+
+/// A version string, parsed as PEP 440 where possible, then as semver, and
+/// kept verbatim otherwise.
+#[derive(Debug)]
+pub enum Version {
+    Pep440(PackageVersion),
+    Semver(SemverVersion),
+    /// A version string that didn't parse under either known scheme, kept
+    /// as-is.
+    Other(String),
+}
+
+impl Version {
+    /// Parses `raw` as PEP 440, falling back to semver, then to
+    /// [`Version::Other`] when neither matches.
+    ///
+    /// # Example
+    /// ```
+    /// let version = Version::parse("1.0");
+    /// let version = Version::parse("unstable");
+    /// ```
+    pub fn parse(raw: &str) -> Self {
+        match PackageVersion::new(raw) {
+            Ok(version) => Self::Pep440(version),
+            Err(_) => match SemverVersion::parse(raw) {
+                Some(version) => Self::Semver(version),
+                None => Self::Other(raw.to_string()),
+            },
+        }
+    }
+
+    /// Parses `text` using the same scheme as `self`, for keeping a package
+    /// pinned to one versioning scheme while still comparing candidates
+    /// against each other, instead of each candidate re-detecting (and
+    /// potentially disagreeing on) its own scheme.
+    ///
+    /// # Example
+    /// ```
+    /// let template = Version::parse("1.0");
+    /// let candidate = template.parse_like("1.1")?;
+    /// ```
+    pub fn parse_like(&self, text: &str) -> Result<Self> {
+        match self {
+            Self::Pep440(_) => PackageVersion::new(text).map(Self::Pep440),
+            Self::Semver(_) => SemverVersion::parse(text)
+                .map(Self::Semver)
+                .ok_or_else(|| anyhow!("{} is not a valid semver version", text)),
+            Self::Other(_) => Ok(Self::Other(text.to_string())),
+        }
+    }
+
+    /// Returns the PEP 440 version underneath, if this scheme is `Pep440`.
+    pub fn as_pep440(&self) -> Option<&PackageVersion> {
+        match self {
+            Self::Pep440(version) => Some(version),
+            Self::Semver(_) | Self::Other(_) => None,
+        }
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Pep440(a), Self::Pep440(b)) => a == b,
+            (Self::Semver(a), Self::Semver(b)) => a == b,
+            (Self::Other(a), Self::Other(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl PartialOrd for Version {
+    /// Orders two versions of the same scheme; `Pep440` compares per PEP
+    /// 440, `Semver` compares per semver precedence, `Other` falls back to a
+    /// plain string compare. Mixed schemes have no defined order.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Pep440(a), Self::Pep440(b)) => a.partial_cmp(b),
+            (Self::Semver(a), Self::Semver(b)) => a.partial_cmp(b),
+            (Self::Other(a), Self::Other(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    /// Preserves whichever scheme produced this version: PEP 440 versions
+    /// round-trip through [`PackageVersion`]'s own `Display`, semver
+    /// versions through [`SemverVersion`]'s, and anything else is printed
+    /// exactly as given to [`Version::parse`].
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Pep440(version) => write!(f, "{}", version),
+            Self::Semver(version) => write!(f, "{}", version),
+            Self::Other(raw) => write!(f, "{}", raw),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SemverVersion, Version};
+
+    #[test]
+    fn check_parses_pep440_versions() {
+        assert!(matches!(Version::parse("1.0"), Version::Pep440(_)));
+    }
+
+    #[test]
+    fn check_falls_back_to_other_for_non_pep440_versions() {
+        // No digits at all, so there's no release segment to anchor a
+        // PEP 440 parse on, and no semver triple either.
+        assert!(matches!(Version::parse("unstable"), Version::Other(_)));
+    }
+
+    #[test]
+    fn check_pep440_versions_compare_by_pep440_rules() {
+        assert!(Version::parse("2.0") > Version::parse("1.0"));
+        assert!(Version::parse("1.0") == Version::parse("v1.0"));
+    }
+
+    #[test]
+    fn check_other_versions_compare_lexicographically() {
+        assert!(Version::parse("beta") > Version::parse("alpha"));
+    }
+
+    #[test]
+    fn check_mixed_schemes_are_incomparable() {
+        let pep440 = Version::parse("1.0");
+        let other = Version::parse("unstable");
+        assert_eq!(pep440.partial_cmp(&other), None);
+        assert_ne!(pep440, other);
+    }
+
+    #[test]
+    fn check_display_preserves_scheme() {
+        assert_eq!(Version::parse("v1.0").to_string(), "1.0");
+        assert_eq!(Version::parse("unstable").to_string(), "unstable");
+    }
+
+    #[test]
+    fn check_semver_precedence() -> Result<(), anyhow::Error> {
+        assert!(
+            SemverVersion::parse("1.0.1").unwrap() > SemverVersion::parse("1.0.0").unwrap()
+        );
+        assert!(
+            SemverVersion::parse("1.0.0").unwrap()
+                > SemverVersion::parse("1.0.0-alpha").unwrap()
+        );
+        assert!(
+            SemverVersion::parse("1.0.0-alpha.1").unwrap()
+                > SemverVersion::parse("1.0.0-alpha").unwrap()
+        );
+        assert!(
+            SemverVersion::parse("1.0.0-alpha.beta").unwrap()
+                > SemverVersion::parse("1.0.0-alpha.1").unwrap()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn check_semver_build_metadata_ignored_in_precedence() {
+        assert!(
+            SemverVersion::parse("1.0.0+build.1").unwrap()
+                == SemverVersion::parse("1.0.0+build.2").unwrap()
+        );
+    }
+
+    #[test]
+    fn check_parse_like_keeps_a_pinned_scheme() -> Result<(), anyhow::Error> {
+        // "1.2.3-beta.1" already parses as a partial PEP 440 match (PEP 440's
+        // validator is unanchored), so use a Pep440 template and a Semver
+        // template separately to exercise both branches of `parse_like`.
+        let pep440_template = Version::parse("1.0");
+        assert!(matches!(
+            pep440_template.parse_like("1.1")?,
+            Version::Pep440(_)
+        ));
+
+        let semver_template = Version::Semver(SemverVersion::parse("1.0.0").unwrap());
+        assert!(matches!(
+            semver_template.parse_like("1.0.1")?,
+            Version::Semver(_)
+        ));
+        assert!(semver_template.parse_like("not-a-version").is_err());
+
+        Ok(())
+    }
+}